@@ -2,7 +2,7 @@
 use self::RecursiveTypeDescription::*;
 
 use super::namespace::mangled_name_of_instance;
-use super::type_names::{compute_debuginfo_type_name, compute_debuginfo_vtable_name};
+use super::type_names::{compute_debuginfo_type_name_symbol, compute_debuginfo_vtable_name};
 use super::utils::{
     create_DIArray, debug_context, get_namespace_for_item, is_node_local_to_unit, DIB,
 };
@@ -374,7 +374,7 @@ fn pointer_or_reference_metadata<'ll, 'tcx>(
 
     let (thin_pointer_size, thin_pointer_align) =
         cx.size_and_align_of(cx.tcx.mk_imm_ptr(cx.tcx.types.unit));
-    let ptr_type_debuginfo_name = compute_debuginfo_type_name(cx.tcx, ptr_type, true);
+    let ptr_type_debuginfo_name = compute_debuginfo_type_name_symbol(cx.tcx, ptr_type, true);
 
     let pointer_type_metadata = match fat_pointer_kind(cx, pointee_type) {
         None => {
@@ -394,8 +394,8 @@ fn pointer_or_reference_metadata<'ll, 'tcx>(
                     thin_pointer_size.bits(),
                     thin_pointer_align.bits() as u32,
                     0, // Ignore DWARF address space.
-                    ptr_type_debuginfo_name.as_ptr().cast(),
-                    ptr_type_debuginfo_name.len(),
+                    ptr_type_debuginfo_name.as_str().as_ptr().cast(),
+                    ptr_type_debuginfo_name.as_str().len(),
                 )
             }
         }
@@ -453,7 +453,7 @@ fn pointer_or_reference_metadata<'ll, 'tcx>(
             composite_type_metadata(
                 cx,
                 ptr_type,
-                &ptr_type_debuginfo_name,
+                ptr_type_debuginfo_name.as_str(),
                 unique_type_id,
                 member_descriptions,
                 NO_SCOPE_METADATA,
@@ -521,7 +521,7 @@ fn subroutine_type_metadata<'ll, 'tcx>(
     };
 
     // This is actually a function pointer, so wrap it in pointer DI.
-    let name = compute_debuginfo_type_name(cx.tcx, fn_ty, false);
+    let name = compute_debuginfo_type_name_symbol(cx.tcx, fn_ty, false);
     let metadata = unsafe {
         llvm::LLVMRustDIBuilderCreatePointerType(
             DIB(cx),
@@ -529,8 +529,8 @@ fn subroutine_type_metadata<'ll, 'tcx>(
             cx.tcx.data_layout.pointer_size.bits(),
             cx.tcx.data_layout.pointer_align.abi.bits() as u32,
             0, // Ignore DWARF address space.
-            name.as_ptr().cast(),
-            name.len(),
+            name.as_str().as_ptr().cast(),
+            name.as_str().len(),
         )
     };
 
@@ -545,8 +545,15 @@ fn dyn_type_metadata<'ll, 'tcx>(
     unique_type_id: UniqueTypeId<'tcx>,
 ) -> &'ll DIType {
     if let ty::Dynamic(..) = dyn_type.kind() {
-        let type_name = compute_debuginfo_type_name(cx.tcx, dyn_type, true);
-        composite_type_metadata(cx, dyn_type, &type_name, unique_type_id, vec![], NO_SCOPE_METADATA)
+        let type_name = compute_debuginfo_type_name_symbol(cx.tcx, dyn_type, true);
+        composite_type_metadata(
+            cx,
+            dyn_type,
+            type_name.as_str(),
+            unique_type_id,
+            vec![],
+            NO_SCOPE_METADATA,
+        )
     } else {
         bug!("Only ty::Dynamic is valid for dyn_type_metadata(). Found {:?} instead.", dyn_type)
     }
@@ -887,13 +894,13 @@ fn foreign_type_metadata<'ll, 'tcx>(
 ) -> &'ll DIType {
     debug!("foreign_type_metadata: {:?}", t);
 
-    let name = compute_debuginfo_type_name(cx.tcx, t, false);
+    let name = compute_debuginfo_type_name_symbol(cx.tcx, t, false);
     let (size, align) = cx.size_and_align_of(t);
     create_struct_stub(
         cx,
         size,
         align,
-        &name,
+        name.as_str(),
         unique_type_id,
         NO_SCOPE_METADATA,
         DIFlags::FlagZero,
@@ -1206,7 +1213,7 @@ fn prepare_struct_metadata<'ll, 'tcx>(
     struct_type: Ty<'tcx>,
     unique_type_id: UniqueTypeId<'tcx>,
 ) -> RecursiveTypeDescription<'ll, 'tcx> {
-    let struct_name = compute_debuginfo_type_name(cx.tcx, struct_type, false);
+    let struct_name = compute_debuginfo_type_name_symbol(cx.tcx, struct_type, false);
 
     let (struct_def_id, variant) = match struct_type.kind() {
         ty::Adt(def, _) => (def.did(), def.non_enum_variant()),
@@ -1220,7 +1227,7 @@ fn prepare_struct_metadata<'ll, 'tcx>(
         cx,
         size,
         align,
-        &struct_name,
+        struct_name.as_str(),
         unique_type_id,
         Some(containing_scope),
         DIFlags::FlagZero,
@@ -1317,13 +1324,13 @@ fn prepare_tuple_metadata<'ll, 'tcx>(
     containing_scope: Option<&'ll DIScope>,
 ) -> RecursiveTypeDescription<'ll, 'tcx> {
     let (size, align) = cx.size_and_align_of(tuple_type);
-    let tuple_name = compute_debuginfo_type_name(cx.tcx, tuple_type, false);
+    let tuple_name = compute_debuginfo_type_name_symbol(cx.tcx, tuple_type, false);
 
     let struct_stub = create_struct_stub(
         cx,
         size,
         align,
-        &tuple_name[..],
+        tuple_name.as_str(),
         unique_type_id,
         containing_scope,
         DIFlags::FlagZero,
@@ -1383,7 +1390,7 @@ fn prepare_union_metadata<'ll, 'tcx>(
     union_type: Ty<'tcx>,
     unique_type_id: UniqueTypeId<'tcx>,
 ) -> RecursiveTypeDescription<'ll, 'tcx> {
-    let union_name = compute_debuginfo_type_name(cx.tcx, union_type, false);
+    let union_name = compute_debuginfo_type_name_symbol(cx.tcx, union_type, false);
 
     let (union_def_id, variant) = match union_type.kind() {
         ty::Adt(def, _) => (def.did(), def.non_enum_variant()),
@@ -1393,7 +1400,7 @@ fn prepare_union_metadata<'ll, 'tcx>(
     let containing_scope = get_namespace_for_item(cx, union_def_id);
 
     let union_metadata_stub =
-        create_union_stub(cx, union_type, &union_name, unique_type_id, containing_scope);
+        create_union_stub(cx, union_type, union_name.as_str(), unique_type_id, containing_scope);
 
     create_and_register_recursive_type_forward_declaration(
         cx,
@@ -1929,7 +1936,7 @@ fn prepare_enum_metadata<'ll, 'tcx>(
     outer_field_tys: Vec<Ty<'tcx>>,
 ) -> RecursiveTypeDescription<'ll, 'tcx> {
     let tcx = cx.tcx;
-    let enum_name = compute_debuginfo_type_name(tcx, enum_type, false);
+    let enum_name = compute_debuginfo_type_name_symbol(tcx, enum_type, false);
 
     let containing_scope = get_namespace_for_item(cx, enum_def_id);
     // FIXME: This should emit actual file metadata for the enum, but we
@@ -2055,8 +2062,8 @@ fn prepare_enum_metadata<'ll, 'tcx>(
                 llvm::LLVMRustDIBuilderCreateUnionType(
                     DIB(cx),
                     None,
-                    enum_name.as_ptr().cast(),
-                    enum_name.len(),
+                    enum_name.as_str().as_ptr().cast(),
+                    enum_name.as_str().len(),
                     file_metadata,
                     UNKNOWN_LINE_NUMBER,
                     layout.size.bits(),
@@ -2196,8 +2203,8 @@ fn prepare_enum_metadata<'ll, 'tcx>(
             llvm::LLVMRustDIBuilderCreateStructType(
                 DIB(cx),
                 Some(containing_scope),
-                enum_name.as_ptr().cast(),
-                enum_name.len(),
+                enum_name.as_str().as_ptr().cast(),
+                enum_name.as_str().len(),
                 file_metadata,
                 UNKNOWN_LINE_NUMBER,
                 layout.size.bits(),
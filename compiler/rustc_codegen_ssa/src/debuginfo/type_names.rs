@@ -11,71 +11,1339 @@
 //   within the brackets).
 // * `"` is treated as the start of a string.
 
+use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_data_structures::stack::ensure_sufficient_stack;
 use rustc_hir::def_id::DefId;
 use rustc_hir::definitions::{DefPathData, DefPathDataName, DisambiguatedDefPathData};
 use rustc_hir::{AsyncGeneratorKind, GeneratorKind, Mutability};
+use rustc_middle::mir::interpret::{get_slice_bytes, ConstValue};
+use rustc_middle::mir::DestructuredConst;
 use rustc_middle::ty::layout::IntegerExt;
-use rustc_middle::ty::subst::{GenericArgKind, SubstsRef};
-use rustc_middle::ty::{self, AdtDef, ExistentialProjection, Ty, TyCtxt};
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::subst::{GenericArg, GenericArgKind, Subst, SubstsRef};
+use rustc_middle::ty::{self, AdtDef, DefIdTree, ExistentialProjection, Ty, TyCtxt, TypeFoldable};
 use rustc_query_system::ich::NodeIdHashingMode;
+use rustc_span::symbol::{kw, sym, Symbol};
 use rustc_target::abi::{Integer, TagEncoding, Variants};
 use smallvec::SmallVec;
 
+use std::cell::RefCell;
 use std::fmt::Write;
 
-// Compute the name of the type as it should be stored in debuginfo. Does not do
-// any caching, i.e., calling the function twice with the same type will also do
-// the work twice. The `qualified` parameter only affects the first level of the
-// type name, further levels (i.e., type parameters) are always fully qualified.
+use self::separators::{
+    pop_arg_separator, pop_auto_trait_separator, pop_close_angle_bracket, push_close_angle_bracket,
+};
+
+mod parse;
+pub mod separators;
+
+pub use self::parse::{
+    parse_debuginfo_type_name, DebuginfoGenericArg, DebuginfoPath, DebuginfoTypeRepr, ParseError,
+};
+
+pub fn provide(providers: &mut Providers) {
+    providers.debuginfo_type_name = debuginfo_type_name;
+}
+
+/// The flavors of debuginfo type names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DebuginfoNameStyle {
+    /// Names that C++ debuggers and .natvis visualizers can deal with, e.g. `ref$<u32>`.
+    CppLike,
+    /// Names that look like Rust source code, e.g. `&u32`.
+    Rust,
+    /// Like [`DebuginfoNameStyle::Rust`], except that arrays and slices are named
+    /// `array<T, N>` and `slice<T>`, because GDB's expression parser cannot deal with
+    /// square brackets in type names (`-Z debuginfo-names-gdb-friendly`).
+    GdbFriendly,
+}
+
+impl DebuginfoNameStyle {
+    /// The style used for the target of the current session, see [`cpp_like_debuginfo`].
+    pub fn for_session(tcx: TyCtxt<'_>) -> Self {
+        if cpp_like_debuginfo(tcx) {
+            DebuginfoNameStyle::CppLike
+        } else if tcx.sess.opts.debugging_opts.debuginfo_names_gdb_friendly {
+            DebuginfoNameStyle::GdbFriendly
+        } else {
+            DebuginfoNameStyle::Rust
+        }
+    }
+}
+
+/// How much of the paths of items is included in debuginfo type names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PathQualification {
+    /// Only the name of the item itself, e.g. `HashMap`.
+    None,
+    /// The path of the item within its crate, e.g. `collections::hash::map::HashMap`.
+    CrateRelative,
+    /// The full path of the item, starting with the name of its crate, e.g.
+    /// `std::collections::hash::map::HashMap`.
+    Full,
+}
+
+/// How the never type is named in debuginfo type names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NeverTypeName {
+    /// `never$` in C++-like names and `!` otherwise.
+    ForStyle,
+    /// `!`, like in Rust source code.
+    Exclamation,
+    /// `never$`, like in C++-like names.
+    CppLike,
+    /// `Never`, for tools that show names to users and can't deal with `!`.
+    Word,
+}
+
+/// Options controlling how debuginfo type names are computed. [`DebuginfoTypeNameOptions::new`]
+/// returns the options the compiler itself uses for the current session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DebuginfoTypeNameOptions {
+    /// Whether the path of the outermost type is qualified. Further levels of the type name
+    /// (i.e., type parameters) are always fully qualified.
+    pub qualified: bool,
+    /// How much of the paths of qualified items is included. Leaving out more than the name
+    /// of the crate makes names ambiguous, so this is meant for tools that show names to
+    /// users rather than for debuggers.
+    pub path_qualification: PathQualification,
+    /// How the never type is named. Other choices than the one for the style are meant for
+    /// tools that show names to users.
+    pub never_type_name: NeverTypeName,
+    /// The flavor of names to generate. This need not match the current session's target,
+    /// e.g. when generating names for a different target.
+    pub style: DebuginfoNameStyle,
+    /// Emit a placeholder instead of ICEing for types that should never reach codegen
+    /// (`-Z debuginfo-names-fallback`).
+    pub unexpected_type_fallback: bool,
+    /// Types nested deeper than this are replaced by a placeholder, so that pathological
+    /// (e.g. machine-generated) types can't exhaust the stack (`-Z debuginfo-names-max-depth`).
+    pub max_depth: usize,
+    /// Names longer than this many bytes are shortened, see
+    /// [`truncate_debuginfo_type_name`] (`-Z debuginfo-names-max-len`).
+    pub max_len: Option<usize>,
+    /// Include named lifetimes in the Rust-style names of references and in the names of
+    /// trait objects, e.g. `&'a T` or `dyn Trait + 'a`, and keep the binders of higher-ranked
+    /// function pointers, e.g. `for<'a> fn(&'a u8)` (`-Z debuginfo-names-lifetimes`).
+    /// Regions are erased by the time codegen asks for type names, so this only makes a
+    /// difference for types that still carry them.
+    pub lifetimes: bool,
+    /// Separate generic arguments, tuple components, etc. by `, ` instead of `,` in C++-like
+    /// names (`-Z debuginfo-names-spaced-separators`). Rust-style names always use `, `
+    /// unless `dwarf_safe` is set.
+    pub spaced_separators: bool,
+    /// Leave out the spaces in separators, for DWARF consumers that choke on spaces in
+    /// `DW_AT_name` (`-Z debuginfo-names-dwarf-safe`). Generic arguments, tuple components,
+    /// etc. are separated by `,`, the traits of trait objects by `+`, and the lengths of arrays
+    /// and SIMD vectors by `;`, e.g. `alloc::vec::Vec<(u8,u16),alloc::alloc::Global>`,
+    /// `[u8;3]`, or `simd<f32;4>`. Spaces that keep words apart, like in `&mut T`,
+    /// `dyn Trait`, or around the `->` of function pointers, are kept. This overrides
+    /// `spaced_separators`. Such names don't round-trip through [`parse_debuginfo_type_name`],
+    /// so `-Z verify-debuginfo-type-names` skips them.
+    pub dwarf_safe: bool,
+    /// Never qualify the names of well-known marker types, i.e. emit `PhantomData<T>` instead
+    /// of `core::marker::PhantomData<T>` (`-Z debuginfo-names-short-markers`).
+    pub short_markers: bool,
+    /// Leave out the path of the most common standard library types, e.g. emit
+    /// `Vec<u8, alloc::alloc::Global>` instead of `alloc::vec::Vec<u8, alloc::alloc::Global>`
+    /// (`-Z debuginfo-names-short-std-paths`). See `has_short_std_path()` for the types
+    /// this applies to. Such names can't always be mapped back to the type they stand for,
+    /// since other crates may define types of the same name.
+    pub short_std_paths: bool,
+    /// Name items of other crates by the path they are publicly re-exported under, e.g.
+    /// `std::collections::HashMap` instead of `std::collections::hash::map::HashMap`, which
+    /// is what users write (`-Z debuginfo-names-reexport-paths`). See
+    /// `visible_item_path()`. Items that aren't reachable through public modules, and items
+    /// of the local crate, keep the path they are defined at.
+    pub reexport_paths: bool,
+    /// Name well-known library newtypes after the type they stand for, e.g. `NonZero<u32>`
+    /// or `nonzero$<u32>` instead of `core::num::NonZeroU32`, `Pin<&mut T>` or
+    /// `pin$<ref_mut$<T> >` instead of `core::pin::Pin<&mut T>`, and `RefCell<T>` or
+    /// `ref_cell$<T>` instead of `core::cell::RefCell<T>`, and `Rc<T>` or `rc$<T>` instead of
+    /// `alloc::rc::Rc<T>`, so that debuggers can show them like the wrapped value, and
+    /// `Range<usize>` or `range$<usize>` instead of `core::ops::range::Range<usize>`
+    /// (`-Z debuginfo-names-aliases`). See
+    /// `debuginfo_type_alias()` for the types that have an alias.
+    pub aliases: bool,
+    /// Append the stable crate id to crate names, e.g. `foo[6e2cc8a1b3c5d7f9]::Type` or
+    /// `foo$6e2cc8a1b3c5d7f9::Type`, so that the types of different crates with the same
+    /// name (like two versions of one dependency) get different names
+    /// (`-Z debuginfo-names-crate-disambiguators`).
+    pub crate_disambiguators: bool,
+    /// Leave out trailing generic arguments of structs, enums, unions, and traits that are the
+    /// same as the defaults of their parameters, e.g. emit `alloc::boxed::Box<u8>` instead of
+    /// `alloc::boxed::Box<u8, alloc::alloc::Global>` (`-Z debuginfo-names-omit-default-args`).
+    pub omit_default_args: bool,
+    /// Spell names canonically wherever the order of their parts is up to us, so that
+    /// names can be compared across builds (`-Z debuginfo-names-canonical`). Associated
+    /// item bindings are sorted by the name of the item (and those of trait objects by their
+    /// values if names collide), and the bounds of opaque types by their full names. (The
+    /// auto traits of trait objects are always sorted by the `DefPathHash` of the trait.)
+    pub canonical: bool,
+    /// Wrap references to slices and `str` in `ref$<...>` in C++-like names, like all other
+    /// references (`-Z debuginfo-names-wrap-slice-refs`). By default they are left unwrapped
+    /// because older versions of WinDbg fail to display them otherwise, and mutable ones are
+    /// only marked as such, e.g. `mut_slice$<slice$<u8> >` or `mut_str$<str>`.
+    pub wrap_slice_refs: bool,
+    /// Name references to `str` `str$<str>` or `str_mut$<str>` in C++-like names, so that
+    /// natvis can tell them apart from other types named `str` and show their length
+    /// (`-Z debuginfo-names-wrap-str-refs`). This has no effect with `wrap_slice_refs`, which
+    /// names them `ref$<str>` or `ref_mut$<str>`.
+    pub wrap_str_refs: bool,
+    /// Mark raw pointers to unsized types, which carry a length or a vtable next to the
+    /// address, as fat in C++-like names, e.g. `ptr_const$<dyn$<core::fmt::Debug>,fat>`, so
+    /// that natvis can tell their layout from the name (`-Z debuginfo-names-fat-ptr-hints`).
+    pub fat_ptr_hints: bool,
+    /// Mark trait objects that are neither `Send` nor `Sync` through one of their traits as
+    /// such, e.g. `dyn Trait + !Send + !Sync` or `dyn$<Trait,not_send$,not_sync$>`, which tells
+    /// whether they can be shared between threads (`-Z debuginfo-names-absent-auto-traits`).
+    /// Trait objects without a principal trait are left alone.
+    pub absent_auto_traits: bool,
+    /// Append the niche information that C++-like names carry in `enum$<...>` to the
+    /// Rust-style names of enums whose discriminant is stored in a niche, e.g.
+    /// `core::option::Option<&u8>{niche#1..=18446744073709551615#Some}`, see
+    /// [`enum_niche_range`] (`-Z debuginfo-names-niche-hints`).
+    pub niche_hints: bool,
+    /// Append the size and alignment in bytes to the names of structs, enums, and unions,
+    /// e.g. `Foo<u8> /*size=16 align=8*/` or `Foo<u8>$size16$align8`, for debugging memory
+    /// layouts (`-Z debuginfo-names-layout-hints`). Types whose layout can't be computed,
+    /// e.g. because they are unsized, are left alone.
+    pub layout_hints: bool,
+    /// Name the unit type `void` in C++-like names, e.g. `ptr_const$<void>` instead of
+    /// `ptr_const$<tuple$<> >`, like in the return type of function pointers, which is always
+    /// `void (*)()` (`-Z debuginfo-names-unit-void`). Parameters of function pointers keep
+    /// `tuple$<>`, since `void (*)(void)` has no parameters in C++. Rust-style names keep `()`,
+    /// and omit it from the return type of function pointers, as Rust itself does.
+    pub unit_void: bool,
+    /// Mark structs whose last field is (or ends in) a slice, `str`, or trait object, e.g.
+    /// `Packet{?Sized}` or `unsized$<Packet>`, so that debuggers don't assume that their
+    /// size is known from their type (`-Z debuginfo-names-unsized-hints`).
+    pub unsized_hints: bool,
+    /// Append the types captured by closures and generators to their names, e.g.
+    /// `{closure_env#0}<T>{captures=(u8, &str)}` or `closure_env$0<T>$captures$<tuple$<u8,str> >`,
+    /// which tells apart closures that only differ in what they capture
+    /// (`-Z debuginfo-names-closure-captures`).
+    pub closure_captures: bool,
+    /// Name closures after the types of their captures in the order they are laid out in
+    /// memory, e.g. `{closure_env#0}<u32, u8>` or `closure_env$0<u32,u8>`, instead of
+    /// `{closure_env#0}<T>`, for debugging the memory of closures
+    /// (`-Z debuginfo-names-closure-layouts`). Closures keep their disambiguator, so that two
+    /// closures of the same function with the same captures still get different names.
+    /// Closures whose layout is not known keep their name.
+    pub closure_layouts: bool,
+    /// Name closures and generators after where they are defined, e.g.
+    /// `{closure@src/main.rs:12:13}` instead of `{closure#0}`, which makes more sense to
+    /// users of tools like profilers. C++-like names and closures without a known location
+    /// keep the disambiguator, as do the names the compiler itself emits.
+    pub closure_locations: bool,
+    /// Append the name of the `async fn` to the name of its state machine, e.g.
+    /// `{async_fn_env#0}{fn=foo}` or `async_fn_env$0$fn$foo`, which tells the state machines
+    /// apart when only their unqualified name is shown (`-Z debuginfo-names-async-fn-names`).
+    pub async_fn_names: bool,
+    /// Append the number of states of generators and async state machines to their names,
+    /// e.g. `{generator_env#0}{states=5}` or `generator_env$0$states$5`, i.e. the number of
+    /// variants of their layout: one per suspend point, plus the unresumed, returned, and
+    /// poisoned states (`-Z debuginfo-names-generator-states`).
+    pub generator_states: bool,
+    /// Append the type to integer const arguments, e.g. `Foo<5u8>` instead of `Foo<5>`, so
+    /// that the width of the value is apparent from the name alone
+    /// (`-Z debuginfo-names-int-suffixes`).
+    pub int_suffixes: bool,
+    /// Name unsigned integer const arguments in hexadecimal, e.g. `Flags<0xff00>` instead of
+    /// `Flags<65280>`, which is easier to read for bitmasks (`-Z debuginfo-names-hex-consts`).
+    /// `0x` is safe for the MSVC debugger's expression parser, so this applies to C++-like
+    /// names as well.
+    pub hex_consts: bool,
+    /// Include the path of the function in the names of function item types, e.g.
+    /// `fn(u8) -> u8 {my_crate::double}` like in diagnostics, or `fn_item$<my_crate::double>`
+    /// in C++-like names, instead of only their signature, which they share with function
+    /// pointers and other functions (`-Z debuginfo-names-fn-item-paths`).
+    pub fn_item_paths: bool,
+    /// The item whose generic parameters the names may refer to, e.g. a polymorphized
+    /// function. Together with `unexpected_type_fallback`, this lets the type parameters that
+    /// stand for `impl Trait` in argument position be named after their bounds, just like
+    /// `impl Trait` in return position, e.g. `impl core::fmt::Debug`, rather than after
+    /// their source text.
+    pub generics_owner: Option<DefId>,
+    /// The module that qualified names are relative to, e.g. the one an IDE is showing. The
+    /// path of the module is left out of the paths of the items inside of it, so that
+    /// `my_crate::shapes::Circle` becomes `Circle` relative to `my_crate::shapes`, and
+    /// `my_crate::shapes::round::Ellipse` becomes `round::Ellipse`. Items outside of the module
+    /// keep their full path, see [`compute_debuginfo_type_name_relative_to`].
+    pub relative_to: Option<DefId>,
+}
+
+impl DebuginfoTypeNameOptions {
+    pub fn new(tcx: TyCtxt<'_>, qualified: bool) -> Self {
+        DebuginfoTypeNameOptions {
+            qualified,
+            path_qualification: PathQualification::Full,
+            never_type_name: NeverTypeName::ForStyle,
+            style: DebuginfoNameStyle::for_session(tcx),
+            unexpected_type_fallback: tcx.sess.opts.debugging_opts.debuginfo_names_fallback,
+            max_depth: tcx.sess.opts.debugging_opts.debuginfo_names_max_depth,
+            max_len: tcx.sess.opts.debugging_opts.debuginfo_names_max_len,
+            lifetimes: tcx.sess.opts.debugging_opts.debuginfo_names_lifetimes,
+            spaced_separators: tcx.sess.opts.debugging_opts.debuginfo_names_spaced_separators,
+            dwarf_safe: tcx.sess.opts.debugging_opts.debuginfo_names_dwarf_safe,
+            short_markers: tcx.sess.opts.debugging_opts.debuginfo_names_short_markers,
+            short_std_paths: tcx.sess.opts.debugging_opts.debuginfo_names_short_std_paths,
+            reexport_paths: tcx.sess.opts.debugging_opts.debuginfo_names_reexport_paths,
+            aliases: tcx.sess.opts.debugging_opts.debuginfo_names_aliases,
+            crate_disambiguators: tcx.sess.opts.debugging_opts.debuginfo_names_crate_disambiguators,
+            omit_default_args: tcx.sess.opts.debugging_opts.debuginfo_names_omit_default_args,
+            canonical: tcx.sess.opts.debugging_opts.debuginfo_names_canonical,
+            wrap_slice_refs: tcx.sess.opts.debugging_opts.debuginfo_names_wrap_slice_refs,
+            wrap_str_refs: tcx.sess.opts.debugging_opts.debuginfo_names_wrap_str_refs,
+            fat_ptr_hints: tcx.sess.opts.debugging_opts.debuginfo_names_fat_ptr_hints,
+            absent_auto_traits: tcx.sess.opts.debugging_opts.debuginfo_names_absent_auto_traits,
+            niche_hints: tcx.sess.opts.debugging_opts.debuginfo_names_niche_hints,
+            layout_hints: tcx.sess.opts.debugging_opts.debuginfo_names_layout_hints,
+            unit_void: tcx.sess.opts.debugging_opts.debuginfo_names_unit_void,
+            unsized_hints: tcx.sess.opts.debugging_opts.debuginfo_names_unsized_hints,
+            closure_captures: tcx.sess.opts.debugging_opts.debuginfo_names_closure_captures,
+            closure_layouts: tcx.sess.opts.debugging_opts.debuginfo_names_closure_layouts,
+            closure_locations: false,
+            async_fn_names: tcx.sess.opts.debugging_opts.debuginfo_names_async_fn_names,
+            generator_states: tcx.sess.opts.debugging_opts.debuginfo_names_generator_states,
+            int_suffixes: tcx.sess.opts.debugging_opts.debuginfo_names_int_suffixes,
+            hex_consts: tcx.sess.opts.debugging_opts.debuginfo_names_hex_consts,
+            fn_item_paths: tcx.sess.opts.debugging_opts.debuginfo_names_fn_item_paths,
+            generics_owner: None,
+            relative_to: None,
+        }
+    }
+
+    fn cpp_like_debuginfo(&self) -> bool {
+        self.style == DebuginfoNameStyle::CppLike
+    }
+}
+
+/// Hooks for customizing the debuginfo names of particular kinds of types, for tools that
+/// embed the compiler. For every (nested) type in a name, the method for its kind is
+/// called. All methods default to the names the compiler generates itself, which are also
+/// available to overrides through [`DebuginfoNameCx::push_default`]. Names of types nested
+/// in the generic arguments, components, etc. of a type go through the emitter again, no
+/// matter whether they are produced by an override or by the default.
+///
+/// See [`compute_debuginfo_type_name_with_emitter`].
+pub trait DebuginfoNameEmitter<'tcx> {
+    /// `bool`, `char`, `str`, `!`, and integer and floating point types.
+    fn push_primitive(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        cx.push_default(t, output);
+    }
+
+    /// Foreign types from `extern { type T; }`.
+    fn push_foreign(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        cx.push_default(t, output);
+    }
+
+    /// Structs, enums, and unions, including SIMD vectors.
+    fn push_adt(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        cx.push_default(t, output);
+    }
+
+    fn push_tuple(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        cx.push_default(t, output);
+    }
+
+    /// References and raw pointers.
+    fn push_pointer(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        cx.push_default(t, output);
+    }
+
+    fn push_array_or_slice(
+        &self,
+        cx: &mut DebuginfoNameCx<'_, 'tcx>,
+        t: Ty<'tcx>,
+        output: &mut String,
+    ) {
+        cx.push_default(t, output);
+    }
+
+    /// Trait objects.
+    fn push_dynamic(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        cx.push_default(t, output);
+    }
+
+    /// Function pointers and function items.
+    fn push_fn(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        cx.push_default(t, output);
+    }
+
+    /// The environments of closures, generators, and async fns and blocks.
+    fn push_closure(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        cx.push_default(t, output);
+    }
+
+    /// Type parameters of polymorphized functions.
+    fn push_param(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        cx.push_default(t, output);
+    }
+
+    /// Opaque types, which only get a name with `-Z debuginfo-names-fallback`.
+    fn push_opaque(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        cx.push_default(t, output);
+    }
+}
+
+/// The emitter that produces the names the compiler generates itself.
+pub struct DefaultDebuginfoNameEmitter;
+
+impl<'tcx> DebuginfoNameEmitter<'tcx> for DefaultDebuginfoNameEmitter {}
+
+/// The state of a type name computation, as seen by a [`DebuginfoNameEmitter`].
+pub struct DebuginfoNameCx<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    qualified: bool,
+    opts: &'a DebuginfoTypeNameOptions,
+    emitter: &'a dyn DebuginfoNameEmitter<'tcx>,
+    visited: &'a mut VisitedTypes<'tcx>,
+}
+
+impl<'a, 'tcx> DebuginfoNameCx<'a, 'tcx> {
+    pub fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    pub fn opts(&self) -> &DebuginfoTypeNameOptions {
+        self.opts
+    }
+
+    /// Whether the path of the type being named is to be qualified.
+    pub fn qualified(&self) -> bool {
+        self.qualified
+    }
+
+    /// Pushes the name the compiler would generate for `t`.
+    pub fn push_default(&mut self, t: Ty<'tcx>, output: &mut String) {
+        push_default_debuginfo_type_name(
+            self.tcx,
+            t,
+            self.qualified,
+            self.opts,
+            self.emitter,
+            output,
+            self.visited,
+        );
+    }
+
+    /// Pushes the name of `t` as a type nested in the one being named, e.g. as one of its
+    /// generic arguments.
+    pub fn push_nested(&mut self, t: Ty<'tcx>, output: &mut String) {
+        push_debuginfo_type_name(self.tcx, t, true, self.opts, self.emitter, output, self.visited);
+    }
+}
+
+// Compute the name of the type as it should be stored in debuginfo. The result
+// is not cached, i.e., calling the function twice with the same type will also do
+// the work twice, but the name is not interned either. Callers that ask for the
+// names of the same types over and over, like the LLVM backend, should go through
+// the `debuginfo_type_name` query instead, which caches the names per `(ty, qualified)`
+// pair, e.g. by calling compute_debuginfo_type_name_symbol(). The `qualified` parameter
+// only affects the first level of the type name, further levels (i.e., type parameters)
+// are always fully qualified.
 pub fn compute_debuginfo_type_name<'tcx>(
     tcx: TyCtxt<'tcx>,
     t: Ty<'tcx>,
     qualified: bool,
 ) -> String {
+    let opts = DebuginfoTypeNameOptions::new(tcx, qualified);
+    compute_debuginfo_type_name_with_options(tcx, t, &opts)
+}
+
+// Like compute_debuginfo_type_name() but returns the name as an interned Symbol. The
+// result is cached by the `debuginfo_type_name` query, i.e., calling the function twice
+// with the same type and `qualified` flag only does the work once and returns the same
+// Symbol. This is the entry point for callers that name the same types many times.
+pub fn compute_debuginfo_type_name_symbol<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    t: Ty<'tcx>,
+    qualified: bool,
+) -> Symbol {
+    tcx.debuginfo_type_name((t, qualified))
+}
+
+// Like compute_debuginfo_type_name() but writes the name to `out`, e.g. for building it
+// into a larger buffer. Like compute_debuginfo_type_name(), it is not cached. Only the names
+// of primitive types go to `out` directly. All other names are still built in a String
+// first and then copied, because building them means going back over what was already
+// written (e.g. to drop a trailing separator), which an arbitrary `fmt::Write` can't do.
+pub fn write_debuginfo_type_name<'tcx, W: Write>(
+    tcx: TyCtxt<'tcx>,
+    t: Ty<'tcx>,
+    qualified: bool,
+    out: &mut W,
+) -> std::fmt::Result {
+    let opts = DebuginfoTypeNameOptions::new(tcx, qualified);
+    match primitive_type_name(t, &opts) {
+        Some(name) if opts.max_len.is_none() => out.write_str(name),
+        _ => out.write_str(&compute_debuginfo_type_name_with_options(tcx, t, &opts)),
+    }
+}
+
+fn debuginfo_type_name<'tcx>(tcx: TyCtxt<'tcx>, (t, qualified): (Ty<'tcx>, bool)) -> Symbol {
+    Symbol::intern(&compute_debuginfo_type_name(tcx, t, qualified))
+}
+
+// Like compute_debuginfo_type_name() but generates names in the given style,
+// regardless of the current session's target. The result is not cached.
+pub fn compute_debuginfo_type_name_for_style<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    t: Ty<'tcx>,
+    qualified: bool,
+    style: DebuginfoNameStyle,
+) -> String {
+    let opts = DebuginfoTypeNameOptions { style, ..DebuginfoTypeNameOptions::new(tcx, qualified) };
+    compute_debuginfo_type_name_with_options(tcx, t, &opts)
+}
+
+// Like compute_debuginfo_type_name() but with explicit options. The result is
+// not cached, i.e., calling the function twice with the same arguments will also
+// do the work twice.
+pub fn compute_debuginfo_type_name_with_options<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    t: Ty<'tcx>,
+    opts: &DebuginfoTypeNameOptions,
+) -> String {
+    // Primitive types are by far the most common ones and their names are known up front,
+    // so there is no need to set up the machinery for walking nested types.
+    let result = match primitive_type_name(t, opts) {
+        Some(name) if opts.max_len.is_none() => name.to_owned(),
+        _ => compute_debuginfo_type_name_with_emitter(tcx, t, opts, &DefaultDebuginfoNameEmitter),
+    };
+    check_debuginfo_type_name(tcx, &result, opts);
+    result
+}
+
+// Like compute_debuginfo_type_name() but leaves the path of `current_mod` out of the names of
+// the items inside of it, e.g. `Circle` instead of `my_crate::shapes::Circle` relative to
+// `my_crate::shapes`. Items outside of `current_mod`, including those of other crates, keep
+// their full path. The result is not cached.
+pub fn compute_debuginfo_type_name_relative_to<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    t: Ty<'tcx>,
+    current_mod: DefId,
+) -> String {
+    let opts = DebuginfoTypeNameOptions {
+        relative_to: Some(current_mod),
+        ..DebuginfoTypeNameOptions::new(tcx, true)
+    };
+    compute_debuginfo_type_name_with_options(tcx, t, &opts)
+}
+
+// Like compute_debuginfo_type_name() but for many types at once. The options and the set of
+// visited types are set up once and shared between the types. Each name is built in a
+// String of its own, sized by debuginfo_type_name_capacity_hint(). The results are not
+// cached.
+pub fn compute_debuginfo_type_names<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    types: &[Ty<'tcx>],
+    qualified: bool,
+) -> Vec<String> {
     let _prof = tcx.prof.generic_activity("compute_debuginfo_type_name");
 
-    let mut result = String::with_capacity(64);
-    let mut visited = FxHashSet::default();
-    push_debuginfo_type_name(tcx, t, qualified, &mut result, &mut visited);
+    let opts = DebuginfoTypeNameOptions::new(tcx, qualified);
+    let emitter = &DefaultDebuginfoNameEmitter;
+    let mut visited = VisitedTypes::default();
+
+    types
+        .iter()
+        .map(|&t| {
+            // Every type is named on its own, the set of visited types is empty again after
+            // each of them.
+            debug_assert!(visited.types.is_empty() && visited.depth == 0);
+
+            let name = match primitive_type_name(t, &opts) {
+                Some(name) if opts.max_len.is_none() => name.to_owned(),
+                _ => {
+                    let mut name = String::with_capacity(debuginfo_type_name_capacity_hint(t));
+                    push_debuginfo_type_name(
+                        tcx,
+                        t,
+                        qualified,
+                        &opts,
+                        emitter,
+                        &mut name,
+                        &mut visited,
+                    );
+                    if let Some(max_len) = opts.max_len {
+                        truncate_debuginfo_type_name(
+                            tcx,
+                            &mut name,
+                            max_len,
+                            opts.cpp_like_debuginfo(),
+                        );
+                    }
+                    name
+                }
+            };
+
+            check_debuginfo_type_name(tcx, &name, &opts);
+            name
+        })
+        .collect()
+}
+
+/// Computes the (qualified) names of the types of the fields of a struct, enum, or union,
+/// together with the names of the fields, e.g. `[(value, "u8"), (next, "&u8")]`. The field
+/// types are substituted with the generic arguments of `adt_ty` and normalized, so they are
+/// the types that the fields have in that instantiation. For enums, the fields of all
+/// variants are returned, variant by variant. Like [`compute_debuginfo_type_names`], the
+/// names are not cached.
+pub fn compute_debuginfo_field_type_names<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    adt_ty: Ty<'tcx>,
+) -> Vec<(Symbol, String)> {
+    let (def, substs) = match *adt_ty.kind() {
+        ty::Adt(def, substs) => (def, substs),
+        _ => bug!("compute_debuginfo_field_type_names: `{:?}` is not an ADT", adt_ty),
+    };
+
+    let fields: Vec<_> = def.all_fields().collect();
+    let field_types: Vec<_> = fields
+        .iter()
+        .map(|field| {
+            tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), field.ty(tcx, substs))
+        })
+        .collect();
+    let names = compute_debuginfo_type_names(tcx, &field_types, true);
+
+    fields.iter().map(|field| field.name).zip(names).collect()
+}
+
+/// Returns the items that the (qualified) debuginfo type name of `t` refers to, in the order
+/// they first appear in the name, e.g. `Vec`, `HashMap`, `RandomState`, and `Global` for
+/// `Vec<HashMap<u8, u8>>`, for tools that build cross-reference indexes. The items are
+/// recorded by a [`DebuginfoNameEmitter`], so the type is walked just like when computing
+/// its name: structs, enums, unions, and extern types, the traits of trait objects and of
+/// opaque types, closures and generators, and function items if their path is part of the
+/// name. See [`collect_type_name_defids_with_options`] for type parameters.
+pub fn collect_type_name_defids<'tcx>(tcx: TyCtxt<'tcx>, t: Ty<'tcx>) -> Vec<DefId> {
+    collect_type_name_defids_with_options(tcx, t, &DebuginfoTypeNameOptions::new(tcx, true))
+}
+
+/// Like [`collect_type_name_defids`] but with explicit options. With a `generics_owner`, the
+/// type parameters of that item are reported as well.
+pub fn collect_type_name_defids_with_options<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    t: Ty<'tcx>,
+    opts: &DebuginfoTypeNameOptions,
+) -> Vec<DefId> {
+    let collector = DefIdCollector::default();
+    let mut name = String::with_capacity(debuginfo_type_name_capacity_hint(t));
+    let mut visited = VisitedTypes::default();
+    push_debuginfo_type_name(tcx, t, opts.qualified, opts, &collector, &mut name, &mut visited);
+    collector.def_ids.into_inner()
+}
+
+// The emitter behind collect_type_name_defids(), which names types like the default one but
+// also records the items that the names refer to.
+#[derive(Default)]
+struct DefIdCollector {
+    def_ids: RefCell<Vec<DefId>>,
+}
+
+impl DefIdCollector {
+    fn record(&self, def_id: DefId) {
+        let mut def_ids = self.def_ids.borrow_mut();
+        if !def_ids.contains(&def_id) {
+            def_ids.push(def_id);
+        }
+    }
+}
+
+impl<'tcx> DebuginfoNameEmitter<'tcx> for DefIdCollector {
+    fn push_foreign(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        if let ty::Foreign(def_id) = *t.kind() {
+            self.record(def_id);
+        }
+        cx.push_default(t, output);
+    }
+
+    fn push_adt(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        if let ty::Adt(def, _) = *t.kind() {
+            self.record(def.did());
+        }
+        cx.push_default(t, output);
+    }
+
+    fn push_dynamic(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        if let ty::Dynamic(trait_data, _) = *t.kind() {
+            if let Some(principal) = trait_data.principal_def_id() {
+                self.record(principal);
+            }
+            for auto_trait in trait_data.auto_traits() {
+                self.record(auto_trait);
+            }
+        }
+        cx.push_default(t, output);
+    }
+
+    fn push_fn(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        if let ty::FnDef(def_id, _) = *t.kind() {
+            if cx.opts().fn_item_paths {
+                self.record(def_id);
+            }
+        }
+        cx.push_default(t, output);
+    }
+
+    fn push_closure(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        if let ty::Closure(def_id, _) | ty::Generator(def_id, ..) = *t.kind() {
+            self.record(def_id);
+        }
+        cx.push_default(t, output);
+    }
+
+    fn push_param(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        if let (ty::Param(param), Some(owner)) = (*t.kind(), cx.opts().generics_owner) {
+            let tcx = cx.tcx();
+            self.record(tcx.generics_of(owner).type_param(&param, tcx).def_id);
+        }
+        cx.push_default(t, output);
+    }
+
+    fn push_opaque(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        // Opaque types are only named after their bounds with the fallback, see
+        // push_default_debuginfo_type_name().
+        if let ty::Opaque(def_id, _) = *t.kind() {
+            if cx.opts().unexpected_type_fallback {
+                for &(bound, _) in cx.tcx().explicit_item_bounds(def_id) {
+                    if let ty::PredicateKind::Trait(pred) = bound.kind().skip_binder() {
+                        self.record(pred.def_id());
+                    }
+                }
+            }
+        }
+        cx.push_default(t, output);
+    }
+}
+
+/// The kind of name computed for a type at its top level, see
+/// [`compute_debuginfo_type_name_classified`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeNameShape {
+    /// `bool`, `u8`, `str`, `!`, ...
+    Primitive,
+    /// The path of a struct, enum or union, like `alloc::vec::Vec<u8>`.
+    Adt,
+    /// An extern type, like `extern type my_crate::Opaque` or `extern_type$<my_crate::Opaque>`.
+    Foreign,
+    /// `(A, B)` or `tuple$<A,B>`, including the unit type.
+    Tuple,
+    /// `[T; N]` or `array$<T,N>`.
+    Array,
+    /// `[T]` or `slice$<T>`.
+    Slice,
+    /// A reference or raw pointer, like `&T` or `ptr_mut$<T>`.
+    Pointer,
+    /// A trait object, like `dyn Trait + Send`.
+    Dyn,
+    /// The signature of a function pointer.
+    FnPtr,
+    /// The signature of a function item.
+    FnDef,
+    /// The environment of a closure or generator, like `{closure_env#0}<T>`.
+    Closure,
+    /// The name of a type parameter.
+    Param,
+    /// An opaque type, like `impl Iterator<Item=u8>`.
+    Opaque,
+    /// A type that should not reach codegen, named by a placeholder.
+    Other,
+}
+
+impl TypeNameShape {
+    pub fn of(t: Ty<'_>) -> TypeNameShape {
+        match t.kind() {
+            ty::Bool | ty::Char | ty::Int(_) | ty::Uint(_) | ty::Float(_) | ty::Str | ty::Never => {
+                TypeNameShape::Primitive
+            }
+            ty::Adt(..) => TypeNameShape::Adt,
+            ty::Foreign(_) => TypeNameShape::Foreign,
+            ty::Tuple(_) => TypeNameShape::Tuple,
+            ty::Array(..) => TypeNameShape::Array,
+            ty::Slice(_) => TypeNameShape::Slice,
+            ty::RawPtr(_) | ty::Ref(..) => TypeNameShape::Pointer,
+            ty::Dynamic(..) => TypeNameShape::Dyn,
+            ty::FnPtr(_) => TypeNameShape::FnPtr,
+            ty::FnDef(..) => TypeNameShape::FnDef,
+            ty::Closure(..) | ty::Generator(..) => TypeNameShape::Closure,
+            ty::Param(_) => TypeNameShape::Param,
+            ty::Opaque(..) => TypeNameShape::Opaque,
+            ty::Error(_)
+            | ty::Infer(_)
+            | ty::Placeholder(..)
+            | ty::Projection(..)
+            | ty::Bound(..)
+            | ty::GeneratorWitness(..) => TypeNameShape::Other,
+        }
+    }
+}
+
+// Like compute_debuginfo_type_name() but also returns the shape of the name, so that tests
+// can check what kind of name a type gets without matching the name itself, which keeps them
+// independent of the details of the formatting.
+pub fn compute_debuginfo_type_name_classified<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    t: Ty<'tcx>,
+    qualified: bool,
+) -> (String, TypeNameShape) {
+    (compute_debuginfo_type_name(tcx, t, qualified), TypeNameShape::of(t))
+}
+
+// Like compute_debuginfo_type_name() but also returns a stable hash of the type, which tools
+// can key on while showing the name to users. Like the hashes of constants in names (see
+// push_const_hash()), it is the same across builds for the same type.
+pub fn compute_debuginfo_type_name_and_id<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    t: Ty<'tcx>,
+    qualified: bool,
+) -> (String, u64) {
+    let hcx = &mut tcx.create_stable_hashing_context();
+    let mut hasher = StableHasher::new();
+    hcx.while_hashing_spans(false, |hcx| {
+        hcx.with_node_id_hashing_mode(NodeIdHashingMode::HashDefPath, |hcx| {
+            t.hash_stable(hcx, &mut hasher);
+        });
+    });
+    let id: u64 = hasher.finish::<Fingerprint>().to_smaller_hash();
+
+    (compute_debuginfo_type_name(tcx, t, qualified), id)
+}
+
+fn check_debuginfo_type_name(tcx: TyCtxt<'_>, name: &str, opts: &DebuginfoTypeNameOptions) {
+    // All closing angle brackets have to go through push_close_angle_bracket().
+    debug_assert!(
+        !opts.cpp_like_debuginfo() || !name.contains(">>"),
+        "C++-like debuginfo type name contains `>>`: {}",
+        name
+    );
+
+    if tcx.sess.opts.debugging_opts.verify_debuginfo_type_names
+        && !opts.cpp_like_debuginfo()
+        && !opts.dwarf_safe
+    {
+        verify_debuginfo_type_name(name);
+    }
+}
+
+// Like compute_debuginfo_type_name_with_options() but lets `emitter` override the names
+// of particular kinds of types. Names produced this way are not checked for being
+// well-formed, that is up to the emitter.
+pub fn compute_debuginfo_type_name_with_emitter<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    t: Ty<'tcx>,
+    opts: &DebuginfoTypeNameOptions,
+    emitter: &dyn DebuginfoNameEmitter<'tcx>,
+) -> String {
+    let _prof = tcx.prof.generic_activity("compute_debuginfo_type_name");
+
+    let mut result = String::with_capacity(debuginfo_type_name_capacity_hint(t));
+    let mut visited = VisitedTypes::default();
+    push_debuginfo_type_name(tcx, t, opts.qualified, opts, emitter, &mut result, &mut visited);
+
+    if let Some(max_len) = opts.max_len {
+        truncate_debuginfo_type_name(tcx, &mut result, max_len, opts.cpp_like_debuginfo());
+    }
+
     result
 }
 
+// The name of `t` if it is a primitive type, i.e., one without any nested types.
+fn primitive_type_name(t: Ty<'_>, opts: &DebuginfoTypeNameOptions) -> Option<&'static str> {
+    Some(match *t.kind() {
+        ty::Bool => "bool",
+        ty::Char => "char",
+        ty::Str => "str",
+        ty::Never => match opts.never_type_name {
+            NeverTypeName::ForStyle if opts.cpp_like_debuginfo() => "never$",
+            NeverTypeName::ForStyle | NeverTypeName::Exclamation => "!",
+            NeverTypeName::CppLike => "never$",
+            NeverTypeName::Word => "Never",
+        },
+        ty::Int(int_ty) => int_ty.name_str(),
+        ty::Uint(uint_ty) => uint_ty.name_str(),
+        ty::Float(float_ty) => float_ty.name_str(),
+        _ => return None,
+    })
+}
+
+// A cheap guess at how many bytes the name of `t` will take, for pre-allocating the buffer
+// the name is built in. Names of types with many generic arguments easily get much longer
+// than the 64 bytes we used to start out with, and growing the buffer over and over again
+// is wasted work. Every type nested in `t` usually contributes a path and a separator, so we
+// assume a fixed number of bytes per type. The estimate does not need to be exact.
+pub fn debuginfo_type_name_capacity_hint(t: Ty<'_>) -> usize {
+    const MIN_CAPACITY: usize = 64;
+    const BYTES_PER_TYPE: usize = 32;
+    // Don't spend more time on guessing for huge types than on computing their names.
+    const MAX_COUNTED_TYPES: usize = 128;
+
+    let nested_types = t
+        .walk()
+        .filter(|arg| !matches!(arg.unpack(), GenericArgKind::Lifetime(_)))
+        .take(MAX_COUNTED_TYPES)
+        .count();
+
+    std::cmp::max(MIN_CAPACITY, nested_types * BYTES_PER_TYPE)
+}
+
+// Shortens `name` if it is longer than `max_len` bytes. The name is cut at the start of
+// one of its components (i.e., right after an opening bracket or a comma) and the brackets
+// that are still open at that point are closed again, so the result stays well-formed.
+// In place of the removed components we emit a stable hash of the full name, which keeps
+// distinct types apart even if their names only differ towards the end.
+//
+// If not even the first component fits, the whole name is replaced by the hash.
+fn truncate_debuginfo_type_name(
+    tcx: TyCtxt<'_>,
+    name: &mut String,
+    max_len: usize,
+    cpp_like_debuginfo: bool,
+) {
+    if name.len() <= max_len {
+        return;
+    }
+
+    let hcx = &mut tcx.create_stable_hashing_context();
+    let mut hasher = StableHasher::new();
+    name.hash_stable(hcx, &mut hasher);
+    // Like for constants, 64 bits of the hash value are plenty for avoiding collisions.
+    let hash: u64 = hasher.finish();
+
+    let marker = if cpp_like_debuginfo {
+        format!("TRUNCATED${:016x}", hash)
+    } else {
+        format!("{{truncated#{:016x}}}", hash)
+    };
+
+    let close_brackets = |open: &[u8], output: &mut String| {
+        for &bracket in open.iter().rev() {
+            match bracket {
+                b'<' => push_close_angle_bracket(cpp_like_debuginfo, output),
+                b'(' => output.push(')'),
+                b'{' => output.push('}'),
+                _ => output.push(']'),
+            }
+        }
+    };
+
+    // The closest cut point to `max_len` found so far, together with the brackets that
+    // are open there.
+    let mut cut: Option<(usize, Vec<u8>)> = None;
+    let mut open = Vec::new();
+    let bytes = name.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let component_start = match bytes[i] {
+            b'<' | b'(' | b'[' => {
+                open.push(bytes[i]);
+                Some(i + 1)
+            }
+            // The `->` of a function pointer's return type.
+            b'>' if i > 0 && bytes[i - 1] == b'-' => None,
+            b'>' | b')' | b']' | b'}' => {
+                open.pop();
+                None
+            }
+            // The types captured by a closure, see `DebuginfoTypeNameOptions::closure_captures`.
+            b'{' if name[i..].starts_with("{captures=") => {
+                open.push(b'{');
+                i += "{captures=".len() - 1;
+                None
+            }
+            // Other braces enclose special names (like `{closure_env#0}`), values, and the
+            // paths of function items, which are not cut apart. The latter can contain
+            // special names themselves, and string values can contain any character.
+            b'{' => {
+                let mut depth = 0;
+                let (mut in_str, mut escaped) = (false, false);
+                let len = name[i..].bytes().position(|b| {
+                    match b {
+                        _ if escaped => escaped = false,
+                        b'\\' if in_str => escaped = true,
+                        b'"' => in_str = !in_str,
+                        _ if in_str => {}
+                        b'{' => depth += 1,
+                        b'}' => depth -= 1,
+                        _ => {}
+                    }
+                    depth == 0
+                });
+                i += len.unwrap_or(0);
+                None
+            }
+            // The disambiguator of a crate name, e.g. `foo[6e2cc8a1b3c5d7f9]`, is not a
+            // component of its own.
+            b'[' if i > 0 && bytes[i - 1].is_ascii_alphanumeric() => {
+                i += name[i..].find(']').unwrap_or(0);
+                None
+            }
+            b',' if bytes.get(i + 1) == Some(&b' ') => Some(i + 2),
+            b',' => Some(i + 1),
+            b'\'' => {
+                // Skip character constants so that brackets in them are not counted.
+                let literal = &name[i + 1..];
+                let len = if literal.starts_with('\\') {
+                    literal[2..].find('\'').map(|end| end + 3)
+                } else {
+                    literal
+                        .chars()
+                        .next()
+                        .map(|c| c.len_utf8())
+                        .filter(|&len| literal[len..].starts_with('\''))
+                        .map(|len| len + 1)
+                };
+                i += len.unwrap_or(0);
+                None
+            }
+            _ => None,
+        };
+
+        if let Some(pos) = component_start {
+            if pos >= max_len {
+                break;
+            }
+
+            let mut closing = String::new();
+            close_brackets(&open, &mut closing);
+            if pos + marker.len() + closing.len() <= max_len {
+                cut = Some((pos, open.clone()));
+            }
+        }
+
+        i += 1;
+    }
+
+    let (pos, open) = cut.unwrap_or_default();
+    name.truncate(pos);
+    name.push_str(&marker);
+    close_brackets(&open, name);
+}
+
+// Checks that `parse_debuginfo_type_name()` understands `name` and that printing
+// the parsed name reproduces it exactly. Enabled by `-Z verify-debuginfo-type-names`.
+fn verify_debuginfo_type_name(name: &str) {
+    match parse_debuginfo_type_name(name) {
+        Ok(repr) if repr.to_string() == name => {}
+        Ok(repr) => bug!("debuginfo type name `{}` does not round-trip, got `{}`", name, repr),
+        Err(err) => bug!("cannot parse debuginfo type name `{}`: {}", name, err),
+    }
+}
+
+/// For an enum whose discriminant is encoded in a niche of its dataful variant, returns the
+/// range of niche values that belong to the dataful variant, together with that variant's
+/// name. These are the values that the `enum$<...>` names of C++-like debuginfo contain, see
+/// `msvc_enum_fallback()`. Returns `None` for all other types, and for enums whose layout
+/// can't be computed.
+///
+/// With `-Z randomize-layout`, the niche can depend on the order of the fields of the dataful
+/// variant, since the first of several equally large niches in memory is used. That order is
+/// shuffled with a seed derived from the `DefPathHash` of the type and `-Z layout-seed`, so
+/// the range is still the same in every build with the same flags, but not across seeds.
+pub fn enum_niche_range<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<(u128, u128, Symbol)> {
+    let def = match *ty.kind() {
+        ty::Adt(def, _) if def.is_enum() => def,
+        _ => return None,
+    };
+
+    let Ok(layout) = tcx.layout_of(tcx.param_env(def.did()).and(ty)) else {
+        return None;
+    };
+
+    if let Variants::Multiple {
+        tag_encoding: TagEncoding::Niche { dataful_variant, .. },
+        tag,
+        variants,
+        ..
+    } = &layout.variants
+    {
+        let dataful_variant_layout = &variants[*dataful_variant];
+
+        // calculate the range of values for the dataful variant
+        let dataful_discriminant_range =
+            dataful_variant_layout.largest_niche().unwrap().scalar.valid_range;
+
+        let min = dataful_discriminant_range.start;
+        let min = tag.value.size(&tcx).truncate(min);
+
+        let max = dataful_discriminant_range.end;
+        let max = tag.value.size(&tcx).truncate(max);
+
+        Some((min, max, def.variant(*dataful_variant).name))
+    } else {
+        None
+    }
+}
+
+/// The state of a walk over a type while naming it: the types on the path from the outermost
+/// type to the one that is currently being named. See [`push_generic_params_with_visited`].
+#[derive(Default)]
+pub struct VisitedTypes<'tcx> {
+    // The types that can contain themselves, for detecting recursive types.
+    types: FxHashSet<Ty<'tcx>>,
+    // The number of types on the path, for limiting how deep we go.
+    depth: usize,
+}
+
+impl<'tcx> VisitedTypes<'tcx> {
+    /// Adds `t` to the path, for callers that push the parts of its name themselves, e.g. its
+    /// path followed by [`push_generic_params_with_visited`] for its generic arguments. `t`
+    /// is then named as a recursive type wherever it shows up in those parts. Returns `false`
+    /// without changing anything if `t` already is on the path.
+    pub fn enter(&mut self, t: Ty<'tcx>) -> bool {
+        if !self.types.insert(t) {
+            return false;
+        }
+        self.depth += 1;
+        true
+    }
+
+    /// Removes `t` from the path again after a successful [`VisitedTypes::enter`].
+    pub fn exit(&mut self, t: Ty<'tcx>) {
+        if self.types.remove(&t) {
+            self.depth -= 1;
+        }
+    }
+}
+
 // Pushes the name of the type as it should be stored in debuginfo on the
-// `output` String. See also compute_debuginfo_type_name().
+// `output` String. See also compute_debuginfo_type_name(). The name is produced
+// by the hook of `emitter` for the kind of the type, which usually ends up in
+// push_default_debuginfo_type_name().
 fn push_debuginfo_type_name<'tcx>(
     tcx: TyCtxt<'tcx>,
     t: Ty<'tcx>,
     qualified: bool,
+    opts: &DebuginfoTypeNameOptions,
+    emitter: &dyn DebuginfoNameEmitter<'tcx>,
     output: &mut String,
-    visited: &mut FxHashSet<Ty<'tcx>>,
+    visited: &mut VisitedTypes<'tcx>,
+) {
+    // Machine-generated code can contain arbitrarily deeply nested types. Instead of
+    // running out of stack space while naming them, we give up at some point.
+    if visited.depth >= opts.max_depth {
+        output.push_str(if opts.cpp_like_debuginfo() { "truncated$" } else { "{truncated}" });
+        return;
+    }
+
+    // Types that contain other types are kept in `visited` while their components
+    // are being processed, so that we notice if we ever encounter a weird
+    // 'recursive type'. Currently, the only way to generate such a type is by
+    // using 'impl trait':
+    //
+    // fn foo() -> impl Copy { foo }
+    //
+    // There's not really a sensible name we can generate,
+    // since we don't include 'impl trait' types (e.g. ty::Opaque)
+    // in the output (unless -Z debuginfo-names-fallback is enabled)
+    //
+    // Since we need to generate *something*, we just
+    // use a dummy string that should make it clear
+    // that something unusual is going on
+    let tracks_recursion = matches!(
+        t.kind(),
+        ty::Adt(..) | ty::Tuple(_) | ty::FnDef(..) | ty::FnPtr(_) | ty::Opaque(..)
+    );
+
+    if tracks_recursion && !visited.types.insert(t) {
+        let cpp_like_debuginfo = opts.cpp_like_debuginfo();
+        output.push_str(if cpp_like_debuginfo { "recursive_type$" } else { "<recursive_type>" });
+        return;
+    }
+
+    visited.depth += 1;
+
+    let cx = &mut DebuginfoNameCx { tcx, qualified, opts, emitter, visited };
+    ensure_sufficient_stack(|| match *t.kind() {
+        ty::Bool | ty::Char | ty::Str | ty::Never | ty::Int(_) | ty::Uint(_) | ty::Float(_) => {
+            emitter.push_primitive(cx, t, output)
+        }
+        ty::Foreign(_) => emitter.push_foreign(cx, t, output),
+        ty::Adt(..) => emitter.push_adt(cx, t, output),
+        ty::Tuple(_) => emitter.push_tuple(cx, t, output),
+        ty::RawPtr(_) | ty::Ref(..) => emitter.push_pointer(cx, t, output),
+        ty::Array(..) | ty::Slice(_) => emitter.push_array_or_slice(cx, t, output),
+        ty::Dynamic(..) => emitter.push_dynamic(cx, t, output),
+        ty::FnDef(..) | ty::FnPtr(_) => emitter.push_fn(cx, t, output),
+        ty::Closure(..) | ty::Generator(..) => emitter.push_closure(cx, t, output),
+        ty::Param(_) => emitter.push_param(cx, t, output),
+        ty::Opaque(..) => emitter.push_opaque(cx, t, output),
+        ty::Error(_)
+        | ty::Infer(_)
+        | ty::Placeholder(..)
+        | ty::Projection(..)
+        | ty::Bound(..)
+        | ty::GeneratorWitness(..) => cx.push_default(t, output),
+    });
+
+    visited.depth -= 1;
+
+    // We only keep the type in 'visited'
+    // for the duration of the body of this method.
+    // It's fine for a particular type
+    // to show up multiple times in one overall type
+    // (e.g. MyType<fn() -> u8, fn() -> u8>
+    //
+    // We only care about avoiding recursing
+    // directly back to the type we're currently
+    // processing
+    if tracks_recursion {
+        visited.types.remove(&t);
+    }
+}
+
+// The names the compiler generates for all kinds of types. Nested types are named through
+// push_debuginfo_type_name(), so that `emitter` gets to name them.
+fn push_default_debuginfo_type_name<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    t: Ty<'tcx>,
+    qualified: bool,
+    opts: &DebuginfoTypeNameOptions,
+    emitter: &dyn DebuginfoNameEmitter<'tcx>,
+    output: &mut String,
+    visited: &mut VisitedTypes<'tcx>,
 ) {
     // When targeting MSVC, emit C++ style type names for compatibility with
     // .natvis visualizers (and perhaps other existing native debuggers?)
-    let cpp_like_debuginfo = cpp_like_debuginfo(tcx);
+    let cpp_like_debuginfo = opts.cpp_like_debuginfo();
 
     match *t.kind() {
-        ty::Bool => output.push_str("bool"),
-        ty::Char => output.push_str("char"),
-        ty::Str => output.push_str("str"),
-        ty::Never => {
+        ty::Bool | ty::Char | ty::Str | ty::Never | ty::Int(_) | ty::Uint(_) | ty::Float(_) => {
+            output.push_str(primitive_type_name(t, opts).unwrap())
+        }
+        ty::Foreign(def_id) => {
+            // The size and layout of `extern type`s are unknown, so they are marked as such
+            // to keep debuggers from trying to read their contents.
             if cpp_like_debuginfo {
-                output.push_str("never$");
+                output.push_str("extern_type$<");
             } else {
-                output.push('!');
+                output.push_str("extern type ");
+            }
+            push_item_name_internal(tcx, def_id, qualified, opts, output);
+            if cpp_like_debuginfo {
+                push_close_angle_bracket(cpp_like_debuginfo, output);
             }
         }
-        ty::Int(int_ty) => output.push_str(int_ty.name_str()),
-        ty::Uint(uint_ty) => output.push_str(uint_ty.name_str()),
-        ty::Float(float_ty) => output.push_str(float_ty.name_str()),
-        ty::Foreign(def_id) => push_item_name(tcx, def_id, qualified, output),
         ty::Adt(def, substs) => {
-            if def.is_enum() && cpp_like_debuginfo {
-                msvc_enum_fallback(tcx, t, def, substs, output, visited);
+            // Enums and unions are always sized, only structs can have an unsized tail.
+            let is_unsized = opts.unsized_hints && def.is_struct() && {
+                let tail = tcx.struct_tail_erasing_lifetimes(t, ty::ParamEnv::reveal_all());
+                matches!(tail.kind(), ty::Dynamic(..) | ty::Slice(_) | ty::Str)
+            };
+            if is_unsized && cpp_like_debuginfo {
+                output.push_str("unsized$<");
+            }
+
+            if def.repr().simd() {
+                // SIMD vectors get a canonical name based on their element type and lane
+                // count, so that debuggers and natvis visualizers can treat them as packed
+                // vectors no matter how they are defined.
+                let (lane_count, element_type) = t.simd_size_and_type(tcx);
+                if cpp_like_debuginfo {
+                    output.push_str("vector$<");
+                    push_debuginfo_type_name(
+                        tcx,
+                        element_type,
+                        true,
+                        opts,
+                        emitter,
+                        output,
+                        visited,
+                    );
+                    push_arg_separator(opts, output);
+                    write!(output, "{}", lane_count).unwrap();
+                    push_close_angle_bracket(cpp_like_debuginfo, output);
+                } else {
+                    output.push_str("simd<");
+                    push_debuginfo_type_name(
+                        tcx,
+                        element_type,
+                        true,
+                        opts,
+                        emitter,
+                        output,
+                        visited,
+                    );
+                    push_len_separator(opts, output);
+                    write!(output, "{}>", lane_count).unwrap();
+                }
+            } else if let Some((cpp_like_alias, rust_alias)) =
+                opts.aliases.then(|| debuginfo_type_alias(tcx, def.did())).flatten()
+            {
+                output.push_str(if cpp_like_debuginfo { cpp_like_alias } else { rust_alias });
+                // Newtypes without generic parameters are named after the type they wrap.
+                let substs = if substs.is_empty() {
+                    let field_ty = def.non_enum_variant().fields[0].ty(tcx, substs);
+                    tcx.intern_substs(&[field_ty.into()])
+                } else {
+                    generic_args_to_print(tcx, def.did(), substs, opts)
+                };
+                push_generic_params_internal(tcx, substs, opts, emitter, output, visited);
+            } else if def.is_enum() && cpp_like_debuginfo {
+                msvc_enum_fallback(tcx, t, def, substs, opts, emitter, output, visited);
+            } else if def.is_union() && cpp_like_debuginfo {
+                // Like enums, unions get a wrapper that visualizers can key off, so that they
+                // don't need to know every union type by name.
+                output.push_str("union$<");
+                push_item_name_internal(tcx, def.did(), true, opts, output);
+                let substs = generic_args_to_print(tcx, def.did(), substs, opts);
+                push_generic_params_internal(tcx, substs, opts, emitter, output, visited);
+                push_close_angle_bracket(cpp_like_debuginfo, output);
             } else {
-                push_item_name(tcx, def.did(), qualified, output);
-                push_generic_params_internal(tcx, substs, output, visited);
+                // The path of well-known marker types doesn't tell anybody anything new,
+                // so it can be left out to keep names short. The same goes for the most
+                // common standard library types, if asked for.
+                let is_short_marker =
+                    opts.short_markers && Some(def.did()) == tcx.lang_items().phantom_data();
+                let is_short_std_path = opts.short_std_paths && has_short_std_path(tcx, def.did());
+                let qualified = qualified && !is_short_marker && !is_short_std_path;
+                push_item_name_internal(tcx, def.did(), qualified, opts, output);
+                let substs = generic_args_to_print(tcx, def.did(), substs, opts);
+                push_generic_params_internal(tcx, substs, opts, emitter, output, visited);
+
+                if opts.niche_hints && def.is_enum() {
+                    if let Some((min, max, dataful_variant)) = enum_niche_range(tcx, t) {
+                        write!(output, "{{niche#{}..={}#{}}}", min, max, dataful_variant).unwrap();
+                    }
+                }
+            }
+
+            if is_unsized {
+                if cpp_like_debuginfo {
+                    push_close_angle_bracket(cpp_like_debuginfo, output);
+                } else {
+                    output.push_str("{?Sized}");
+                }
+            }
+
+            // SIMD vectors already carry their lane count.
+            if opts.layout_hints && !def.repr().simd() {
+                // Unlike in `msvc_enum_fallback()`, a missing layout is not a bug here, since
+                // names are also computed for types that are still generic.
+                match tcx.layout_of(ty::ParamEnv::reveal_all().and(t)) {
+                    Ok(layout) if !layout.abi.is_unsized() => {
+                        let (size, align) = (layout.size.bytes(), layout.align.abi.bytes());
+                        if cpp_like_debuginfo {
+                            write!(output, "$size{}$align{}", size, align).unwrap();
+                        } else {
+                            write!(output, " /*size={} align={}*/", size, align).unwrap();
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        ty::Tuple(component_types) if component_types.is_empty() && opts.unit_void => {
+            if cpp_like_debuginfo {
+                output.push_str("void");
+            } else {
+                output.push_str("()");
             }
         }
         ty::Tuple(component_types) => {
@@ -86,8 +1354,8 @@ fn push_debuginfo_type_name<'tcx>(
             }
 
             for component_type in component_types {
-                push_debuginfo_type_name(tcx, component_type, true, output, visited);
-                push_arg_separator(cpp_like_debuginfo, output);
+                push_debuginfo_type_name(tcx, component_type, true, opts, emitter, output, visited);
+                push_arg_separator(opts, output);
             }
             if !component_types.is_empty() {
                 pop_arg_separator(output);
@@ -95,6 +1363,10 @@ fn push_debuginfo_type_name<'tcx>(
 
             if cpp_like_debuginfo {
                 push_close_angle_bracket(cpp_like_debuginfo, output);
+            } else if component_types.len() == 1 {
+                // Like in Rust, a tuple of one has a trailing comma, so that it can't be
+                // mistaken for a parenthesized type, e.g. `(u8,)`.
+                output.push_str(",)");
             } else {
                 output.push(')');
             }
@@ -113,22 +1385,55 @@ fn push_debuginfo_type_name<'tcx>(
                 }
             }
 
-            push_debuginfo_type_name(tcx, inner_type, qualified, output, visited);
+            push_debuginfo_type_name(tcx, inner_type, qualified, opts, emitter, output, visited);
 
             if cpp_like_debuginfo {
+                // Whether the pointer is fat depends on the tail of the pointee, which can be
+                // hidden in a struct, so it is spelled out if asked for.
+                if opts.fat_ptr_hints {
+                    let tail =
+                        tcx.struct_tail_erasing_lifetimes(inner_type, ty::ParamEnv::reveal_all());
+                    if matches!(tail.kind(), ty::Dynamic(..) | ty::Slice(_) | ty::Str) {
+                        push_arg_separator(opts, output);
+                        output.push_str("fat");
+                    }
+                }
                 push_close_angle_bracket(cpp_like_debuginfo, output);
             }
         }
-        ty::Ref(_, inner_type, mutbl) => {
+        ty::Ref(region, inner_type, mutbl) => {
             // Slices and `&str` are treated like C++ pointers when computing debug
             // info for MSVC debugger. However, wrapping these types' names in a synthetic type
             // causes the .natvis engine for WinDbg to fail to display their data, so we opt these
-            // types out to aid debugging in MSVC.
-            let is_slice_or_str = matches!(*inner_type.kind(), ty::Slice(_) | ty::Str);
+            // types out to aid debugging in MSVC. Debuggers that can deal with the wrapper can
+            // opt back in with `-Z debuginfo-names-wrap-slice-refs`.
+            let is_slice_or_str =
+                matches!(*inner_type.kind(), ty::Slice(_) | ty::Str) && !opts.wrap_slice_refs;
+            // `&str` can get a wrapper of its own instead, which is unknown to WinDbg, so that
+            // natvis can key off it without breaking the visualization of plain `str`.
+            let is_wrapped_str = is_slice_or_str && inner_type.is_str() && opts.wrap_str_refs;
+            // Mutable references that are opted out still get a marker, so that `&mut str` and
+            // `&str` don't end up with the same name. Immutable ones stay unwrapped.
+            let is_marked_mut = is_slice_or_str && !is_wrapped_str && mutbl == Mutability::Mut;
 
             if !cpp_like_debuginfo {
                 output.push('&');
+                // Erased and anonymous regions have no name that would be useful here.
+                if opts.lifetimes && region.has_name() {
+                    write!(output, "{} ", region).unwrap();
+                }
                 output.push_str(mutbl.prefix_str());
+            } else if is_wrapped_str {
+                match mutbl {
+                    Mutability::Not => output.push_str("str$<"),
+                    Mutability::Mut => output.push_str("str_mut$<"),
+                }
+            } else if is_marked_mut {
+                if inner_type.is_str() {
+                    output.push_str("mut_str$<");
+                } else {
+                    output.push_str("mut_slice$<");
+                }
             } else if !is_slice_or_str {
                 match mutbl {
                     Mutability::Not => output.push_str("ref$<"),
@@ -136,55 +1441,95 @@ fn push_debuginfo_type_name<'tcx>(
                 }
             }
 
-            push_debuginfo_type_name(tcx, inner_type, qualified, output, visited);
+            push_debuginfo_type_name(tcx, inner_type, qualified, opts, emitter, output, visited);
 
-            if cpp_like_debuginfo && !is_slice_or_str {
+            if cpp_like_debuginfo && (!is_slice_or_str || is_wrapped_str || is_marked_mut) {
                 push_close_angle_bracket(cpp_like_debuginfo, output);
             }
         }
         ty::Array(inner_type, len) => {
             if cpp_like_debuginfo {
                 output.push_str("array$<");
-                push_debuginfo_type_name(tcx, inner_type, true, output, visited);
-                match len.val() {
-                    ty::ConstKind::Param(param) => write!(output, ",{}>", param.name).unwrap(),
-                    _ => write!(output, ",{}>", len.eval_usize(tcx, ty::ParamEnv::reveal_all()))
-                        .unwrap(),
-                }
+                push_debuginfo_type_name(tcx, inner_type, true, opts, emitter, output, visited);
+                push_arg_separator(opts, output);
+                push_array_len(tcx, len, opts, output);
+                push_close_angle_bracket(cpp_like_debuginfo, output);
+            } else if opts.style == DebuginfoNameStyle::GdbFriendly {
+                output.push_str("array<");
+                push_debuginfo_type_name(tcx, inner_type, true, opts, emitter, output, visited);
+                push_arg_separator(opts, output);
+                push_array_len(tcx, len, opts, output);
+                output.push('>');
             } else {
                 output.push('[');
-                push_debuginfo_type_name(tcx, inner_type, true, output, visited);
-                match len.val() {
-                    ty::ConstKind::Param(param) => write!(output, "; {}]", param.name).unwrap(),
-                    _ => write!(output, "; {}]", len.eval_usize(tcx, ty::ParamEnv::reveal_all()))
-                        .unwrap(),
-                }
+                push_debuginfo_type_name(tcx, inner_type, true, opts, emitter, output, visited);
+                push_len_separator(opts, output);
+                push_array_len(tcx, len, opts, output);
+                output.push(']');
             }
         }
         ty::Slice(inner_type) => {
+            let gdb_friendly = opts.style == DebuginfoNameStyle::GdbFriendly;
             if cpp_like_debuginfo {
                 output.push_str("slice$<");
+            } else if gdb_friendly {
+                output.push_str("slice<");
             } else {
                 output.push('[');
             }
 
-            push_debuginfo_type_name(tcx, inner_type, true, output, visited);
+            push_debuginfo_type_name(tcx, inner_type, true, opts, emitter, output, visited);
 
-            if cpp_like_debuginfo {
+            if cpp_like_debuginfo || gdb_friendly {
                 push_close_angle_bracket(cpp_like_debuginfo, output);
             } else {
                 output.push(']');
             }
         }
-        ty::Dynamic(ref trait_data, ..) => {
+        ty::Dynamic(ref trait_data, region) => {
             let auto_traits: SmallVec<[DefId; 4]> = trait_data.auto_traits().collect();
+            // A trait object without a single trait, which can only be left over from code
+            // that had errors, still gets a well-formed name: `dyn '_` or `dyn$<>`.
+            let has_traits = trait_data.principal().is_some() || !auto_traits.is_empty();
+
+            // `Send` and `Sync` decide whether the object can be shared between threads, so
+            // their absence is worth mentioning, if asked for. A supertrait of the principal
+            // trait counts as present.
+            let mut absent_auto_traits: SmallVec<[&str; 2]> = SmallVec::new();
+            if let Some(principal) = trait_data.principal().filter(|_| opts.absent_auto_traits) {
+                let present = supertrait_def_ids(tcx, principal.def_id());
+                let markers = [
+                    (tcx.get_diagnostic_item(sym::Send), "!Send", "not_send$"),
+                    (tcx.lang_items().sync_trait(), "!Sync", "not_sync$"),
+                ];
+                for (def_id, rust_marker, cpp_like_marker) in markers {
+                    let Some(def_id) = def_id else { continue };
+                    if !present.contains(&def_id) && !auto_traits.contains(&def_id) {
+                        absent_auto_traits.push(if cpp_like_debuginfo {
+                            cpp_like_marker
+                        } else {
+                            rust_marker
+                        });
+                    }
+                }
+            }
 
+            // The `'a` in `dyn Trait + 'a`. Like for references, it is only included if
+            // requested and if there is a name for it.
+            let region_bound =
+                if opts.lifetimes && region.has_name() { Some(region) } else { None };
+
+            // FIXME: Once `dyn*` types exist, `ty::Dynamic` will also carry a `DynKind`,
+            // and `dyn* Trait` (`dyn_star$<...>` in C++-like names) has to be told apart
+            // from `dyn Trait` here, since the two have different layouts.
             let has_enclosing_parens = if cpp_like_debuginfo {
                 output.push_str("dyn$<");
                 false
             } else {
-                if trait_data.len() > 1 && auto_traits.len() != 0 {
-                    // We need enclosing parens because there is more than one trait
+                // Like the pretty printer used for diagnostics, we only add parentheses if
+                // there is a lifetime bound, e.g. `&(dyn Trait + Send + 'a)`, but not for
+                // `&dyn Trait + Send`, so that names look the same as in error messages.
+                if region_bound.is_some() && has_traits {
                     output.push_str("(dyn ");
                     true
                 } else {
@@ -196,110 +1541,171 @@ fn push_debuginfo_type_name<'tcx>(
             if let Some(principal) = trait_data.principal() {
                 let principal =
                     tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), principal);
-                push_item_name(tcx, principal.def_id, qualified, output);
-                let principal_has_generic_params =
-                    push_generic_params_internal(tcx, principal.substs, output, visited);
 
-                let projection_bounds: SmallVec<[_; 4]> = trait_data
+                let mut projection_bounds: SmallVec<[_; 4]> = trait_data
                     .projection_bounds()
                     .map(|bound| {
                         let ExistentialProjection { item_def_id, term, .. } = bound.skip_binder();
-                        // FIXME(associated_const_equality): allow for consts here
-                        (item_def_id, term.ty().unwrap())
+                        (item_def_id, term)
                     })
                     .collect();
-
-                if projection_bounds.len() != 0 {
-                    if principal_has_generic_params {
-                        // push_generic_params_internal() above added a `>` but we actually
-                        // want to add more items to that list, so remove that again.
-                        pop_close_angle_bracket(output);
-                    }
-
-                    for (item_def_id, ty) in projection_bounds {
-                        push_arg_separator(cpp_like_debuginfo, output);
-
-                        if cpp_like_debuginfo {
-                            output.push_str("assoc$<");
-                            push_item_name(tcx, item_def_id, false, output);
-                            push_arg_separator(cpp_like_debuginfo, output);
-                            push_debuginfo_type_name(tcx, ty, true, output, visited);
-                            push_close_angle_bracket(cpp_like_debuginfo, output);
-                        } else {
-                            push_item_name(tcx, item_def_id, false, output);
-                            output.push('=');
-                            push_debuginfo_type_name(tcx, ty, true, output, visited);
-                        }
-                    }
-
-                    push_close_angle_bracket(cpp_like_debuginfo, output);
+                // The projection bounds come ordered by the stable hashes of their items.
+                // Canonical names sort them by name instead, so that their order doesn't
+                // depend on how items are hashed. Items of different traits can have the same
+                // name, in which case their values decide.
+                if opts.canonical {
+                    projection_bounds.sort_by(|&(a, a_term), &(b, b_term)| {
+                        tcx.item_name(a).as_str().cmp(tcx.item_name(b).as_str()).then_with(|| {
+                            debuginfo_term_name(tcx, a_term, opts)
+                                .cmp(&debuginfo_term_name(tcx, b_term, opts))
+                        })
+                    });
                 }
 
+                let substs = trait_args_to_print(tcx, principal.def_id, t, principal.substs, opts);
+                push_trait_with_bindings(
+                    tcx,
+                    principal.def_id,
+                    substs,
+                    &projection_bounds,
+                    qualified,
+                    opts,
+                    emitter,
+                    output,
+                    visited,
+                );
+
                 if auto_traits.len() != 0 {
-                    push_auto_trait_separator(cpp_like_debuginfo, output);
+                    push_auto_trait_separator(opts, output);
                 }
             }
 
             if auto_traits.len() != 0 {
-                let mut auto_traits: SmallVec<[String; 4]> = auto_traits
-                    .into_iter()
-                    .map(|def_id| {
-                        let mut name = String::with_capacity(20);
-                        push_item_name(tcx, def_id, true, &mut name);
-                        name
-                    })
-                    .collect();
-                auto_traits.sort_unstable();
+                // Auto traits are ordered by the `DefPathHash` of the trait, which doesn't
+                // change with incremental compilation or with the order of items in the
+                // source, and doesn't take rendering every name up front. Interning the
+                // existential predicates already puts them in that order (see
+                // `ExistentialPredicate::stable_cmp`), so there is nothing left to sort.
+                debug_assert!(
+                    auto_traits
+                        .windows(2)
+                        .all(|w| tcx.def_path_hash(w[0]) <= tcx.def_path_hash(w[1]))
+                );
 
                 for auto_trait in auto_traits {
-                    output.push_str(&auto_trait);
-                    push_auto_trait_separator(cpp_like_debuginfo, output);
+                    push_item_name_internal(tcx, auto_trait, true, opts, output);
+                    push_auto_trait_separator(opts, output);
                 }
 
                 pop_auto_trait_separator(output);
             }
 
+            for marker in absent_auto_traits {
+                push_auto_trait_separator(opts, output);
+                output.push_str(marker);
+            }
+
+            if let Some(region) = region_bound {
+                let region = region.to_string();
+                if cpp_like_debuginfo {
+                    // `'` would start a character literal for the MSVC debugger.
+                    if has_traits {
+                        push_arg_separator(opts, output);
+                    }
+                    output.push_str("lifetime$");
+                    output.push_str(region.trim_start_matches('\''));
+                } else {
+                    if has_traits {
+                        push_auto_trait_separator(opts, output);
+                    }
+                    output.push_str(&region);
+                }
+            } else if !has_traits && !cpp_like_debuginfo {
+                output.push_str("'_");
+            }
+
             if cpp_like_debuginfo {
                 push_close_angle_bracket(cpp_like_debuginfo, output);
             } else if has_enclosing_parens {
                 output.push(')');
             }
         }
+        ty::FnDef(def_id, substs) if opts.fn_item_paths && cpp_like_debuginfo => {
+            // There is no room for the path in the syntax of C++ function pointers, so it
+            // takes the place of the signature.
+            output.push_str("fn_item$<");
+            push_item_name_internal(tcx, def_id, true, opts, output);
+            push_generic_params_internal(tcx, substs, opts, emitter, output, visited);
+            push_close_angle_bracket(cpp_like_debuginfo, output);
+        }
         ty::FnDef(..) | ty::FnPtr(_) => {
-            // We've encountered a weird 'recursive type'
-            // Currently, the only way to generate such a type
-            // is by using 'impl trait':
-            //
-            // fn foo() -> impl Copy { foo }
-            //
-            // There's not really a sensible name we can generate,
-            // since we don't include 'impl trait' types (e.g. ty::Opaque)
-            // in the output
-            //
-            // Since we need to generate *something*, we just
-            // use a dummy string that should make it clear
-            // that something unusual is going on
-            if !visited.insert(t) {
-                output.push_str(if cpp_like_debuginfo {
-                    "recursive_type$"
-                } else {
-                    "<recursive_type>"
-                });
-                return;
-            }
-
-            let sig =
-                tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), t.fn_sig(tcx));
+            // With `opts.lifetimes`, the named lifetimes of higher-ranked signatures are kept
+            // together with their binder, e.g. `for<'a> fn(&'a u8) -> &'a u8`, instead of
+            // being erased. Signatures with projections would need to be normalized, which
+            // can't be done with bound lifetimes in them, so they are erased regardless.
+            let poly_sig = t.fn_sig(tcx);
+            let bound_lifetimes: SmallVec<[Symbol; 4]> = if opts.lifetimes
+                && !cpp_like_debuginfo
+                && !poly_sig.skip_binder().has_projections()
+            {
+                poly_sig
+                    .bound_vars()
+                    .iter()
+                    .filter_map(|var| match var {
+                        ty::BoundVariableKind::Region(ty::BrNamed(_, name))
+                            if name != kw::UnderscoreLifetime =>
+                        {
+                            Some(name)
+                        }
+                        _ => None,
+                    })
+                    .collect()
+            } else {
+                SmallVec::new()
+            };
+            let sig = if bound_lifetimes.is_empty() {
+                tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), poly_sig)
+            } else {
+                tcx.erase_regions(poly_sig.skip_binder())
+            };
 
             if cpp_like_debuginfo {
                 // Format as a C++ function pointer: return_type (*)(params...)
+                // The calling convention is not part of that shape, so it is added as a
+                // separate token, e.g. `extern_C_unwind$ void (*)()`.
+                if sig.abi != rustc_target::spec::abi::Abi::Rust {
+                    output.push_str("extern_");
+                    output.extend(
+                        sig.abi.name().chars().map(|c| if c.is_alphanumeric() { c } else { '_' }),
+                    );
+                    output.push_str("$ ");
+                }
+
                 if sig.output().is_unit() {
                     output.push_str("void");
                 } else {
-                    push_debuginfo_type_name(tcx, sig.output(), true, output, visited);
+                    push_debuginfo_type_name(
+                        tcx,
+                        sig.output(),
+                        true,
+                        opts,
+                        emitter,
+                        output,
+                        visited,
+                    );
                 }
                 output.push_str(" (*)(");
             } else {
+                if !bound_lifetimes.is_empty() {
+                    output.push_str("for<");
+                    for name in &bound_lifetimes {
+                        output.push_str(name.as_str());
+                        push_arg_separator(opts, output);
+                    }
+                    pop_arg_separator(output);
+                    output.push_str("> ");
+                }
+
                 output.push_str(sig.unsafety.prefix_str());
 
                 if sig.abi != rustc_target::spec::abi::Abi::Rust {
@@ -311,39 +1717,51 @@ fn push_debuginfo_type_name<'tcx>(
                 output.push_str("fn(");
             }
 
-            if !sig.inputs().is_empty() {
-                for &parameter_type in sig.inputs() {
-                    push_debuginfo_type_name(tcx, parameter_type, true, output, visited);
-                    push_arg_separator(cpp_like_debuginfo, output);
+            // C variadic functions end their parameter list in `...`, both in C++ and in
+            // Rust syntax, so it is treated like one more parameter here. That way it gets
+            // the same separator as all the other parameters.
+            for &parameter_type in sig.inputs() {
+                if cpp_like_debuginfo && opts.unit_void && parameter_type.is_unit() {
+                    // `void (*)(void)` would be a function without parameters in C++.
+                    output.push_str("tuple$<>");
+                } else {
+                    push_debuginfo_type_name(
+                        tcx,
+                        parameter_type,
+                        true,
+                        opts,
+                        emitter,
+                        output,
+                        visited,
+                    );
                 }
-                pop_arg_separator(output);
+                push_arg_separator(opts, output);
             }
 
             if sig.c_variadic {
-                if !sig.inputs().is_empty() {
-                    output.push_str(", ...");
-                } else {
-                    output.push_str("...");
-                }
+                output.push_str("...");
+                push_arg_separator(opts, output);
+            }
+
+            if !sig.inputs().is_empty() || sig.c_variadic {
+                pop_arg_separator(output);
             }
 
             output.push(')');
 
             if !cpp_like_debuginfo && !sig.output().is_unit() {
                 output.push_str(" -> ");
-                push_debuginfo_type_name(tcx, sig.output(), true, output, visited);
+                push_debuginfo_type_name(tcx, sig.output(), true, opts, emitter, output, visited);
             }
 
-            // We only keep the type in 'visited'
-            // for the duration of the body of this method.
-            // It's fine for a particular function type
-            // to show up multiple times in one overall type
-            // (e.g. MyType<fn() -> u8, fn() -> u8>
-            //
-            // We only care about avoiding recursing
-            // directly back to the type we're currently
-            // processing
-            visited.remove(&t);
+            if let ty::FnDef(def_id, substs) = *t.kind() {
+                if opts.fn_item_paths {
+                    output.push_str(" {");
+                    push_item_name_internal(tcx, def_id, true, opts, output);
+                    push_generic_params_internal(tcx, substs, opts, emitter, output, visited);
+                    output.push('}');
+                }
+            }
         }
         ty::Closure(def_id, substs) | ty::Generator(def_id, substs, ..) => {
             // Name will be "{closure_env#0}<T1, T2, ...>", "{generator_env#0}<T1, T2, ...>", or
@@ -352,16 +1770,46 @@ fn push_debuginfo_type_name<'tcx>(
 
             if qualified {
                 let parent_def_id = DefId { index: def_key.parent.unwrap(), ..def_id };
-                push_item_name(tcx, parent_def_id, true, output);
+                push_item_name_internal(tcx, parent_def_id, true, opts, output);
                 output.push_str("::");
             }
 
+            if opts.closure_layouts && t.is_closure() {
+                if let Ok(layout) = tcx.layout_of(ty::ParamEnv::reveal_all().and(t)) {
+                    // The fields of the layout are the captures, in the order they were
+                    // captured.
+                    let upvar_tys: SmallVec<[Ty<'tcx>; 8]> =
+                        substs.as_closure().upvar_tys().collect();
+                    push_disambiguated_special_name(
+                        "closure_env",
+                        def_key.disambiguated_data.disambiguator,
+                        closure_location(tcx, def_id, opts).as_deref(),
+                        cpp_like_debuginfo,
+                        output,
+                    );
+                    if !upvar_tys.is_empty() {
+                        output.push('<');
+                        for index in layout.fields.index_by_increasing_offset() {
+                            let upvar_ty = upvar_tys[index];
+                            push_debuginfo_type_name(
+                                tcx, upvar_ty, true, opts, emitter, output, visited,
+                            );
+                            push_arg_separator(opts, output);
+                        }
+                        pop_arg_separator(output);
+                        push_close_angle_bracket(cpp_like_debuginfo, output);
+                    }
+                    return;
+                }
+            }
+
             let mut label = String::with_capacity(20);
             write!(&mut label, "{}_env", generator_kind_label(tcx.generator_kind(def_id))).unwrap();
 
             push_disambiguated_special_name(
                 &label,
                 def_key.disambiguated_data.disambiguator,
+                closure_location(tcx, def_id, opts).as_deref(),
                 cpp_like_debuginfo,
                 output,
             );
@@ -376,12 +1824,98 @@ fn push_debuginfo_type_name<'tcx>(
 
             // Truncate the substs to the length of the above generics. This will cut off
             // anything closure- or generator-specific.
-            let substs = substs.truncate_to(tcx, generics);
-            push_generic_params_internal(tcx, substs, output, visited);
+            let enclosing_substs = substs.truncate_to(tcx, generics);
+            push_generic_params_internal(tcx, enclosing_substs, opts, emitter, output, visited);
+
+            let is_async_fn = matches!(
+                tcx.generator_kind(def_id),
+                Some(GeneratorKind::Async(AsyncGeneratorKind::Fn))
+            );
+            if opts.async_fn_names && is_async_fn {
+                output.push_str(if cpp_like_debuginfo { "$fn$" } else { "{fn=" });
+                push_item_name_str(tcx.item_name(enclosing_fn_def_id).as_str(), opts, output);
+                if !cpp_like_debuginfo {
+                    output.push('}');
+                }
+            }
+
+            if opts.generator_states && matches!(t.kind(), ty::Generator(..)) {
+                // The layout of a generator that is still generic may not be known.
+                if let Ok(layout) = tcx.layout_of(ty::ParamEnv::reveal_all().and(t)) {
+                    if let Variants::Multiple { variants, .. } = &layout.variants {
+                        if cpp_like_debuginfo {
+                            write!(output, "$states${}", variants.len()).unwrap();
+                        } else {
+                            write!(output, "{{states={}}}", variants.len()).unwrap();
+                        }
+                    }
+                }
+            }
+
+            if opts.closure_captures {
+                let upvars_ty = match *t.kind() {
+                    ty::Closure(..) => substs.as_closure().tupled_upvars_ty(),
+                    _ => substs.as_generator().tupled_upvars_ty(),
+                };
+                output.push_str(if cpp_like_debuginfo { "$captures$<" } else { "{captures=" });
+                push_debuginfo_type_name(tcx, upvars_ty, true, opts, emitter, output, visited);
+                if cpp_like_debuginfo {
+                    push_close_angle_bracket(cpp_like_debuginfo, output);
+                } else {
+                    output.push('}');
+                }
+            }
         }
-        // Type parameters from polymorphized functions.
-        ty::Param(_) => {
-            output.push_str(&format!("{:?}", t));
+        // Type parameters from polymorphized functions. Argument-position `impl Trait` is a
+        // type parameter in disguise, which is named after its bounds like opaque types (see
+        // below) if we know where it comes from.
+        ty::Param(param)
+            if opts.unexpected_type_fallback
+                && opts.generics_owner.map_or(false, |owner| {
+                    let param = tcx.generics_of(owner).type_param(&param, tcx);
+                    matches!(param.kind, ty::GenericParamDefKind::Type { synthetic: true, .. })
+                }) =>
+        {
+            let mut traits: SmallVec<[ty::TraitRef<'tcx>; 4]> = SmallVec::new();
+            let mut projections: SmallVec<[ty::ProjectionPredicate<'tcx>; 4]> = SmallVec::new();
+
+            for &(predicate, _) in tcx.predicates_of(opts.generics_owner.unwrap()).predicates {
+                match tcx.erase_late_bound_regions(predicate.kind()) {
+                    ty::PredicateKind::Trait(pred) if pred.self_ty() == t => {
+                        traits.push(pred.trait_ref)
+                    }
+                    ty::PredicateKind::Projection(pred) if pred.projection_ty.self_ty() == t => {
+                        projections.push(pred)
+                    }
+                    _ => {}
+                }
+            }
+
+            push_impl_trait_name(tcx, traits, &projections, opts, emitter, output, visited);
+        }
+        // Type parameters are named like in the source code, just like const parameters (see
+        // push_const_param()), rather than through the `Debug` impl of `Ty`, whose output
+        // is not meant to be stable.
+        ty::Param(param) => {
+            output.push_str(param.name.as_str());
+        }
+        ty::Opaque(def_id, substs) if opts.unexpected_type_fallback => {
+            // Opaque types should not reach codegen either (see below), but if they do, their
+            // bounds make for a better name than the generic placeholder, e.g.
+            // `impl core::iter::traits::iterator::Iterator<Item=u8>`.
+            let mut traits: SmallVec<[ty::TraitRef<'tcx>; 4]> = SmallVec::new();
+            let mut projections: SmallVec<[ty::ProjectionPredicate<'tcx>; 4]> = SmallVec::new();
+
+            for &(bound, _) in tcx.explicit_item_bounds(def_id) {
+                let bound = bound.subst(tcx, substs).kind();
+                match tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), bound) {
+                    ty::PredicateKind::Trait(pred) => traits.push(pred.trait_ref),
+                    ty::PredicateKind::Projection(pred) => projections.push(pred),
+                    _ => {}
+                }
+            }
+
+            push_impl_trait_name(tcx, traits, &projections, opts, emitter, output, visited);
         }
         ty::Error(_)
         | ty::Infer(_)
@@ -390,81 +1924,254 @@ fn push_debuginfo_type_name<'tcx>(
         | ty::Bound(..)
         | ty::Opaque(..)
         | ty::GeneratorWitness(..) => {
-            bug!(
-                "debuginfo: Trying to create type name for \
-                  unexpected type: {:?}",
-                t
-            );
+            // None of these should ever show up in well-formed codegen input, so by default
+            // we ICE in order to catch bugs. `-Z debuginfo-names-fallback` opts into emitting
+            // a clearly marked placeholder instead, which is useful when working on
+            // experimental features or when debugging broken incremental builds.
+            //
+            // These kinds are deliberately left without a proper name: inference variables,
+            // placeholders, bound variables, and errors only exist during type checking,
+            // projections and opaque types are normalized away before codegen, and generator
+            // witnesses are never the type of a value. Note that there is intentionally no
+            // wildcard arm in this match: a new kind of type (e.g. a refinement of an
+            // existing one) has to be given a name here, or be added to this list, before
+            // the compiler builds again.
+            //
+            // FIXME: If projections and opaque types get merged into a single kind of alias
+            // type, it has to keep rendering like they do today: projections (and any other
+            // alias that is normalized away) as this placeholder, opaque types by their
+            // bounds (see `DebuginfoNameEmitter::push_opaque`).
+            if !opts.unexpected_type_fallback {
+                bug!(
+                    "debuginfo: Trying to create type name for \
+                      unexpected type: {:?}",
+                    t
+                );
+            }
+
+            if cpp_like_debuginfo {
+                output.push_str("unknown$");
+            } else {
+                let kind = match t.kind() {
+                    ty::Error(_) => "Error",
+                    ty::Infer(_) => "Infer",
+                    ty::Placeholder(..) => "Placeholder",
+                    ty::Projection(..) => "Projection",
+                    ty::Bound(..) => "Bound",
+                    ty::GeneratorWitness(..) => "GeneratorWitness",
+                    _ => unreachable!(),
+                };
+                write!(output, "<unknown:{}>", kind).unwrap();
+            }
+        }
+    }
+
+    /// MSVC names enums differently than other platforms so that the debugging visualization
+    // format (natvis) is able to understand enums and render the active variant correctly in the
+    // debugger. For more information, look in `src/etc/natvis/intrinsic.natvis` and
+    // `EnumMemberDescriptionFactor::create_member_descriptions`.
+    fn msvc_enum_fallback<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        ty: Ty<'tcx>,
+        def: AdtDef<'tcx>,
+        substs: SubstsRef<'tcx>,
+        opts: &DebuginfoTypeNameOptions,
+        emitter: &dyn DebuginfoNameEmitter<'tcx>,
+        output: &mut String,
+        visited: &mut VisitedTypes<'tcx>,
+    ) {
+        // Layout errors (e.g. for enums too big for the target) are reported elsewhere, if the
+        // enum is actually used. The name just lacks the information that natvis needs to
+        // pick the active variant then.
+        let layout = match tcx.layout_of(tcx.param_env(def.did()).and(ty)) {
+            Ok(layout) => Some(layout),
+            Err(err) => {
+                debug!("msvc_enum_fallback: no layout for `{}`: {}", ty, err);
+                None
+            }
+        };
+
+        output.push_str("enum$<");
+        let qualified = !(opts.short_std_paths && has_short_std_path(tcx, def.did()));
+        push_item_name_internal(tcx, def.did(), qualified, opts, output);
+        let substs = generic_args_to_print(tcx, def.did(), substs, opts);
+        push_generic_params_internal(tcx, substs, opts, emitter, output, visited);
+
+        // Under `-Z randomize-layout` the niche depends on the layout seed, but it is
+        // reproducible for a given seed, see enum_niche_range().
+        if let Some((min, max, dataful_variant_name)) = enum_niche_range(tcx, ty) {
+            output.push_str(&format!(", {}, {}, {}", min, max, dataful_variant_name));
+        } else if let Some(Variants::Single { index: variant_idx }) =
+            layout.as_ref().map(|layout| &layout.variants)
+        {
+            // Uninhabited enums can't be constructed and should never need to be visualized so
+            // skip this step for them.
+            if def.variants().len() != 0 {
+                let variant = def.variant(*variant_idx).name.as_str();
+
+                output.push_str(&format!(", {}", variant));
+            }
         }
+        push_close_angle_bracket(true, output);
     }
 
-    /// MSVC names enums differently than other platforms so that the debugging visualization
-    // format (natvis) is able to understand enums and render the active variant correctly in the
-    // debugger. For more information, look in `src/etc/natvis/intrinsic.natvis` and
-    // `EnumMemberDescriptionFactor::create_member_descriptions`.
-    fn msvc_enum_fallback<'tcx>(
+    // Pushes the name of a trait together with its generic arguments and associated item
+    // bindings, e.g. `core::ops::function::Fn<(), Output=()>`. `substs` must not contain
+    // the `Self` type.
+    fn push_trait_with_bindings<'tcx>(
         tcx: TyCtxt<'tcx>,
-        ty: Ty<'tcx>,
-        def: AdtDef<'tcx>,
+        trait_def_id: DefId,
         substs: SubstsRef<'tcx>,
+        bindings: &[(DefId, ty::Term<'tcx>)],
+        qualified: bool,
+        opts: &DebuginfoTypeNameOptions,
+        emitter: &dyn DebuginfoNameEmitter<'tcx>,
         output: &mut String,
-        visited: &mut FxHashSet<Ty<'tcx>>,
+        visited: &mut VisitedTypes<'tcx>,
     ) {
-        let layout = tcx.layout_of(tcx.param_env(def.did()).and(ty)).expect("layout error");
+        let cpp_like_debuginfo = opts.cpp_like_debuginfo();
 
-        output.push_str("enum$<");
-        push_item_name(tcx, def.did(), true, output);
-        push_generic_params_internal(tcx, substs, output, visited);
-
-        if let Variants::Multiple {
-            tag_encoding: TagEncoding::Niche { dataful_variant, .. },
-            tag,
-            variants,
-            ..
-        } = &layout.variants
-        {
-            let dataful_variant_layout = &variants[*dataful_variant];
+        push_item_name_internal(tcx, trait_def_id, qualified, opts, output);
+        let has_generic_params =
+            push_generic_params_internal(tcx, substs, opts, emitter, output, visited);
 
-            // calculate the range of values for the dataful variant
-            let dataful_discriminant_range =
-                dataful_variant_layout.largest_niche().unwrap().scalar.valid_range;
+        if bindings.is_empty() {
+            return;
+        }
 
-            let min = dataful_discriminant_range.start;
-            let min = tag.value.size(&tcx).truncate(min);
+        let mut bindings: SmallVec<[_; 4]> = bindings.iter().copied().collect();
+        if opts.canonical {
+            bindings.sort_by(|&(a, _), &(b, _)| {
+                tcx.item_name(a).as_str().cmp(tcx.item_name(b).as_str())
+            });
+        }
 
-            let max = dataful_discriminant_range.end;
-            let max = tag.value.size(&tcx).truncate(max);
+        if has_generic_params {
+            // push_generic_params_internal() above added a `>` but we actually
+            // want to add more items to that list, so remove that again.
+            pop_close_angle_bracket(output);
+            push_arg_separator(opts, output);
+        } else {
+            // push_generic_params_internal() did not add `<...>`, so we open
+            // angle brackets here.
+            output.push('<');
+        }
 
-            let dataful_variant_name = def.variant(*dataful_variant).name.as_str();
+        for (item_def_id, term) in bindings {
+            if cpp_like_debuginfo {
+                output.push_str("assoc$<");
+                push_item_name_internal(tcx, item_def_id, false, opts, output);
+                push_arg_separator(opts, output);
+            } else {
+                push_item_name_internal(tcx, item_def_id, false, opts, output);
+                output.push('=');
+            }
 
-            output.push_str(&format!(", {}, {}, {}", min, max, dataful_variant_name));
-        } else if let Variants::Single { index: variant_idx } = &layout.variants {
-            // Uninhabited enums can't be constructed and should never need to be visualized so
-            // skip this step for them.
-            if def.variants().len() != 0 {
-                let variant = def.variant(*variant_idx).name.as_str();
+            match term {
+                ty::Term::Ty(ty) => {
+                    push_debuginfo_type_name(tcx, ty, true, opts, emitter, output, visited)
+                }
+                ty::Term::Const(ct) => push_const_param(tcx, ct, opts, output),
+            }
 
-                output.push_str(&format!(", {}", variant));
+            if cpp_like_debuginfo {
+                push_close_angle_bracket(cpp_like_debuginfo, output);
             }
+
+            push_arg_separator(opts, output);
         }
-        push_close_angle_bracket(true, output);
+
+        pop_arg_separator(output);
+        push_close_angle_bracket(cpp_like_debuginfo, output);
     }
 
-    const NON_CPP_AUTO_TRAIT_SEPARATOR: &str = " + ";
+    // Pushes the name of an `impl Trait` type with the given bounds, e.g.
+    // `impl core::iter::traits::iterator::Iterator<Item=u8>`.
+    fn push_impl_trait_name<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        mut traits: SmallVec<[ty::TraitRef<'tcx>; 4]>,
+        projections: &[ty::ProjectionPredicate<'tcx>],
+        opts: &DebuginfoTypeNameOptions,
+        emitter: &dyn DebuginfoNameEmitter<'tcx>,
+        output: &mut String,
+        visited: &mut VisitedTypes<'tcx>,
+    ) {
+        let cpp_like_debuginfo = opts.cpp_like_debuginfo();
+
+        // The implicit `Sized` bound is only worth mentioning if there is nothing else.
+        if traits.len() > 1 {
+            let sized_trait = tcx.lang_items().sized_trait();
+            traits.retain(|trait_ref| Some(trait_ref.def_id) != sized_trait);
+        }
 
-    fn push_auto_trait_separator(cpp_like_debuginfo: bool, output: &mut String) {
-        if cpp_like_debuginfo {
-            push_arg_separator(cpp_like_debuginfo, output);
+        let has_enclosing_parens = if cpp_like_debuginfo {
+            output.push_str("impl$<");
+            false
+        } else if traits.len() > 1 {
+            output.push_str("(impl ");
+            true
         } else {
-            output.push_str(NON_CPP_AUTO_TRAIT_SEPARATOR);
+            output.push_str("impl ");
+            false
+        };
+
+        let mut trait_names: SmallVec<[String; 4]> = traits
+            .iter()
+            .map(|trait_ref| {
+                let bindings: SmallVec<[_; 4]> = projections
+                    .iter()
+                    .filter(|pred| pred.projection_ty.trait_def_id(tcx) == trait_ref.def_id)
+                    .map(|pred| (pred.projection_ty.item_def_id, pred.term))
+                    .collect();
+                // The first generic argument is the `impl Trait` type itself.
+                let trait_substs =
+                    generic_args_to_print(tcx, trait_ref.def_id, trait_ref.substs, opts);
+                let trait_substs = tcx.mk_substs(trait_substs.iter().skip(1));
+
+                let mut name = String::new();
+                push_trait_with_bindings(
+                    tcx,
+                    trait_ref.def_id,
+                    trait_substs,
+                    &bindings,
+                    true,
+                    opts,
+                    emitter,
+                    &mut name,
+                    visited,
+                );
+                name
+            })
+            .collect();
+
+        // The bounds are in source order, which is not a property of the type.
+        if opts.canonical {
+            trait_names.sort_unstable();
+        }
+
+        for trait_name in trait_names {
+            output.push_str(&trait_name);
+            push_auto_trait_separator(opts, output);
+        }
+
+        if !traits.is_empty() {
+            pop_auto_trait_separator(output);
+        }
+
+        if cpp_like_debuginfo {
+            push_close_angle_bracket(cpp_like_debuginfo, output);
+        } else if has_enclosing_parens {
+            output.push(')');
         }
     }
 
-    fn pop_auto_trait_separator(output: &mut String) {
-        if output.ends_with(NON_CPP_AUTO_TRAIT_SEPARATOR) {
-            output.truncate(output.len() - NON_CPP_AUTO_TRAIT_SEPARATOR.len());
+    fn push_auto_trait_separator(opts: &DebuginfoTypeNameOptions, output: &mut String) {
+        let cpp_like_debuginfo = opts.cpp_like_debuginfo();
+        if opts.dwarf_safe && !cpp_like_debuginfo {
+            output.push_str(separators::COMPACT_AUTO_TRAIT_SEPARATOR);
         } else {
-            pop_arg_separator(output);
+            let spaced_separators = opts.spaced_separators && !opts.dwarf_safe;
+            separators::push_auto_trait_separator(cpp_like_debuginfo, spaced_separators, output);
         }
     }
 }
@@ -495,7 +2202,8 @@ pub fn compute_debuginfo_vtable_name<'tcx>(
     trait_ref: Option<ty::PolyExistentialTraitRef<'tcx>>,
     kind: VTableNameKind,
 ) -> String {
-    let cpp_like_debuginfo = cpp_like_debuginfo(tcx);
+    let opts = DebuginfoTypeNameOptions::new(tcx, true);
+    let cpp_like_debuginfo = opts.cpp_like_debuginfo();
 
     let mut vtable_name = String::with_capacity(64);
 
@@ -505,8 +2213,9 @@ pub fn compute_debuginfo_vtable_name<'tcx>(
         vtable_name.push('<');
     }
 
-    let mut visited = FxHashSet::default();
-    push_debuginfo_type_name(tcx, t, true, &mut vtable_name, &mut visited);
+    let emitter = &DefaultDebuginfoNameEmitter;
+    let mut visited = VisitedTypes::default();
+    push_debuginfo_type_name(tcx, t, true, &opts, emitter, &mut vtable_name, &mut visited);
 
     if cpp_like_debuginfo {
         vtable_name.push_str(", ");
@@ -517,9 +2226,16 @@ pub fn compute_debuginfo_vtable_name<'tcx>(
     if let Some(trait_ref) = trait_ref {
         let trait_ref =
             tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), trait_ref);
-        push_item_name(tcx, trait_ref.def_id, true, &mut vtable_name);
-        visited.clear();
-        push_generic_params_internal(tcx, trait_ref.substs, &mut vtable_name, &mut visited);
+        push_item_name_internal(tcx, trait_ref.def_id, true, &opts, &mut vtable_name);
+        visited.types.clear();
+        push_generic_params_internal(
+            tcx,
+            trait_args_to_print(tcx, trait_ref.def_id, t, trait_ref.substs, &opts),
+            &opts,
+            emitter,
+            &mut vtable_name,
+            &mut visited,
+        );
     } else {
         vtable_name.push_str("_");
     }
@@ -539,16 +2255,148 @@ pub fn compute_debuginfo_vtable_name<'tcx>(
     vtable_name
 }
 
+/// Computes the name of a pointer to a trait object, like `&dyn Trait`, together with the
+/// name of the vtable that such a pointer points to when it was created from a value of type
+/// `concrete_ty` (see [`compute_debuginfo_vtable_name`]), so that tools can link the two up.
+///
+/// Returns `None` if `dyn_ptr_ty` is not a pointer to a trait object.
+pub fn compute_debuginfo_dyn_pointer_names<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    dyn_ptr_ty: Ty<'tcx>,
+    concrete_ty: Ty<'tcx>,
+) -> Option<(String, String)> {
+    let trait_ref = match *dyn_ptr_ty.builtin_deref(true)?.ty.kind() {
+        ty::Dynamic(ref trait_data, _) => trait_data.principal(),
+        _ => return None,
+    };
+
+    let value_name = compute_debuginfo_type_name(tcx, dyn_ptr_ty, true);
+    let vtable_name =
+        compute_debuginfo_vtable_name(tcx, concrete_ty, trait_ref, VTableNameKind::GlobalVariable);
+
+    Some((value_name, vtable_name))
+}
+
 pub fn push_item_name(tcx: TyCtxt<'_>, def_id: DefId, qualified: bool, output: &mut String) {
+    let opts = DebuginfoTypeNameOptions::new(tcx, qualified);
+    push_item_name_internal(tcx, def_id, qualified, &opts, output);
+}
+
+// Pushes the name of `t` without the path leading up to it, e.g. `HashMap<u32, u8,
+// std::collections::hash::map::RandomState>` instead of
+// `std::collections::hash::map::HashMap<...>`. This is the regular name with only the
+// outermost path left unqualified, so it has the same generic arguments, aliases, and
+// C++-like wrappers as the full name. Generic arguments are still fully qualified. Types
+// without a path of their own, like primitives or tuples, get their regular name.
+pub fn push_short_type_name<'tcx>(tcx: TyCtxt<'tcx>, t: Ty<'tcx>, output: &mut String) {
+    let _prof = tcx.prof.generic_activity("compute_debuginfo_type_name");
+    let opts = DebuginfoTypeNameOptions::new(tcx, false);
+    let emitter = &DefaultDebuginfoNameEmitter;
+    let mut visited = VisitedTypes::default();
+    push_debuginfo_type_name(tcx, t, false, &opts, emitter, output, &mut visited);
+}
+
+fn push_item_name_internal(
+    tcx: TyCtxt<'_>,
+    def_id: DefId,
+    qualified: bool,
+    opts: &DebuginfoTypeNameOptions,
+    output: &mut String,
+) {
     let def_key = tcx.def_key(def_id);
-    if qualified {
-        if let Some(parent) = def_key.parent {
-            push_item_name(tcx, DefId { krate: def_id.krate, index: parent }, true, output);
+    if !qualified || opts.path_qualification == PathQualification::None {
+        push_unqualified_item_name(tcx, def_id, def_key.disambiguated_data, opts, output);
+        return;
+    }
+
+    // Items can be nested arbitrarily deep (e.g. in macro-generated module trees), so the
+    // path is collected up front instead of recursing once per parent.
+    let visible_path = opts.reexport_paths.then(|| visible_item_path(tcx, def_id)).flatten();
+    let mut path: SmallVec<[(DefId, DisambiguatedDefPathData); 8]> = SmallVec::new();
+    let mut current = if visible_path.is_some() { None } else { Some((def_id, def_key)) };
+    for &(def_id, disambiguated_data) in visible_path.iter().flatten() {
+        if opts.path_qualification == PathQualification::CrateRelative
+            && disambiguated_data.data == DefPathData::CrateRoot
+            && !path.is_empty()
+        {
+            break;
+        }
+        path.push((def_id, disambiguated_data));
+    }
+    while let Some((def_id, def_key)) = current {
+        if opts.path_qualification == PathQualification::CrateRelative
+            && def_key.disambiguated_data.data == DefPathData::CrateRoot
+            && !path.is_empty()
+        {
+            break;
+        }
+        path.push((def_id, def_key.disambiguated_data));
+        current = def_key.parent.map(|parent| {
+            let parent_def_id = DefId { krate: def_id.krate, index: parent };
+            (parent_def_id, tcx.def_key(parent_def_id))
+        });
+    }
+
+    // The path up to and including the module that names are relative to is shared with it,
+    // so it is left out. The item itself is always kept.
+    if let Some(relative_to) = opts.relative_to {
+        if let Some(len) = path.iter().skip(1).position(|&(def_id, _)| def_id == relative_to) {
+            path.truncate(len + 1);
+        }
+    }
+
+    for (i, &(def_id, disambiguated_data)) in path.iter().rev().enumerate() {
+        if i > 0 {
             output.push_str("::");
         }
+        push_unqualified_item_name(tcx, def_id, disambiguated_data, opts, output);
+    }
+}
+
+// The path of an item of another crate through the public modules and re-exports it can be
+// reached by, innermost first, like the pretty printer's visible paths, e.g. `HashMap`,
+// `collections`, `std` for `std::collections::hash::map::HashMap`. Returns `None` for local
+// items and for items that aren't reachable that way, e.g. items inside of functions.
+fn visible_item_path(
+    tcx: TyCtxt<'_>,
+    def_id: DefId,
+) -> Option<SmallVec<[(DefId, DisambiguatedDefPathData); 8]>> {
+    if def_id.is_local() {
+        return None;
     }
 
-    push_unqualified_item_name(tcx, def_id, def_key.disambiguated_data, output);
+    let visible_parent_map = tcx.visible_parent_map(());
+    let mut path: SmallVec<[(DefId, DisambiguatedDefPathData); 8]> = SmallVec::new();
+    let mut current = def_id;
+    loop {
+        let mut disambiguated_data = tcx.def_key(current).disambiguated_data;
+        if disambiguated_data.data == DefPathData::CrateRoot {
+            path.push((current, disambiguated_data));
+            return Some(path);
+        }
+
+        let visible_parent = *visible_parent_map.get(&current)?;
+        if path.iter().any(|&(def_id, _)| def_id == visible_parent) {
+            return None;
+        }
+
+        // An item that is visible through a re-export may be re-exported under another
+        // name, e.g. `pub use self::sys::ext as unix;`, so its name is looked up among the
+        // children of its visible parent.
+        if let DefPathData::TypeNs(ref mut name) = disambiguated_data.data {
+            if Some(visible_parent) != tcx.parent(current) {
+                *name = tcx
+                    .module_children(visible_parent)
+                    .iter()
+                    .filter(|child| child.res.opt_def_id() == Some(current))
+                    .find(|child| child.vis.is_public() && child.ident.name != kw::Underscore)
+                    .map(|child| child.ident.name)?;
+            }
+        }
+
+        path.push((current, disambiguated_data));
+        current = visible_parent;
+    }
 }
 
 fn generator_kind_label(generator_kind: Option<GeneratorKind>) -> &'static str {
@@ -561,28 +2409,68 @@ fn generator_kind_label(generator_kind: Option<GeneratorKind>) -> &'static str {
     }
 }
 
+// Pushes a name like `{closure#0}` or `closure$0`. With a `location` (see
+// closure_location()), Rust-style names become `{closure@src/main.rs:12:13}` instead.
 fn push_disambiguated_special_name(
     label: &str,
     disambiguator: u32,
+    location: Option<&str>,
     cpp_like_debuginfo: bool,
     output: &mut String,
 ) {
+    // Debuggers and natvis visualizers rely on the label to tell the kinds of closures and
+    // generators apart (e.g. `async_fn_env$0` vs `generator_env$0`), so it must not contain
+    // anything that could be mistaken for the separators added here.
+    debug_assert!(!label.contains(|c| matches!(c, '$' | '#' | '{' | '}')), "{}", label);
+
     if cpp_like_debuginfo {
         write!(output, "{}${}", label, disambiguator).unwrap();
+    } else if let Some(location) = location {
+        write!(output, "{{{}@{}}}", label, location).unwrap();
     } else {
         write!(output, "{{{}#{}}}", label, disambiguator).unwrap();
     }
 }
 
+// The place a closure or generator is defined at, like `src/main.rs:12:13`, if it is known
+// and `DebuginfoTypeNameOptions::closure_locations` asks for it. File paths can't be part of
+// C++-like names, so they never get one.
+fn closure_location(
+    tcx: TyCtxt<'_>,
+    def_id: DefId,
+    opts: &DebuginfoTypeNameOptions,
+) -> Option<String> {
+    if !opts.closure_locations || opts.cpp_like_debuginfo() {
+        return None;
+    }
+
+    let span = tcx.def_span(def_id);
+    if span.is_dummy() {
+        return None;
+    }
+
+    let loc = tcx.sess.source_map().lookup_char_pos(span.lo());
+    Some(format!("{}:{}:{}", loc.file.name.prefer_remapped(), loc.line, loc.col.to_usize() + 1))
+}
+
 fn push_unqualified_item_name(
     tcx: TyCtxt<'_>,
     def_id: DefId,
     disambiguated_data: DisambiguatedDefPathData,
+    opts: &DebuginfoTypeNameOptions,
     output: &mut String,
 ) {
     match disambiguated_data.data {
         DefPathData::CrateRoot => {
-            output.push_str(tcx.crate_name(def_id.krate).as_str());
+            push_item_name_str(tcx.crate_name(def_id.krate).as_str(), opts, output);
+            if opts.crate_disambiguators {
+                let stable_crate_id = tcx.stable_crate_id(def_id.krate).to_u64();
+                if opts.cpp_like_debuginfo() {
+                    write!(output, "${:016x}", stable_crate_id).unwrap();
+                } else {
+                    write!(output, "[{:016x}]", stable_crate_id).unwrap();
+                }
+            }
         }
         DefPathData::ClosureExpr => {
             let label = generator_kind_label(tcx.generator_kind(def_id));
@@ -590,19 +2478,21 @@ fn push_unqualified_item_name(
             push_disambiguated_special_name(
                 label,
                 disambiguated_data.disambiguator,
-                cpp_like_debuginfo(tcx),
+                closure_location(tcx, def_id, opts).as_deref(),
+                opts.cpp_like_debuginfo(),
                 output,
             );
         }
         _ => match disambiguated_data.data.name() {
             DefPathDataName::Named(name) => {
-                output.push_str(name.as_str());
+                push_item_name_str(name.as_str(), opts, output);
             }
             DefPathDataName::Anon { namespace } => {
                 push_disambiguated_special_name(
                     namespace.as_str(),
                     disambiguated_data.disambiguator,
-                    cpp_like_debuginfo(tcx),
+                    None,
+                    opts.cpp_like_debuginfo(),
                     output,
                 );
             }
@@ -610,34 +2500,71 @@ fn push_unqualified_item_name(
     };
 }
 
+fn push_item_name_str(name: &str, opts: &DebuginfoTypeNameOptions, output: &mut String) {
+    if opts.cpp_like_debuginfo() {
+        push_cpp_like_escaped_name(name, output);
+    } else {
+        output.push_str(name);
+    }
+}
+
+/// Pushes `name` with the characters that MSVC's expression parser chokes on (see the notes
+/// at the top of this file) escaped, for use in C++-like names. Each of `#`, `{`, `}`, `<`,
+/// `>`, `[`, `]`, `"`, and `$` is replaced by `$` followed by the two lowercase hex digits
+/// of its ASCII code, e.g. `#` by `$23`. Since `$` itself is escaped, tools can reverse the
+/// escaping by replacing each `$` and the two digits after it by the character they encode.
+///
+/// Names of items never contain these characters when they come from Rust source code, but
+/// names created by other means (e.g. by a compiler plugin or a custom driver) might.
+pub fn push_cpp_like_escaped_name(name: &str, output: &mut String) {
+    let is_hostile = |c: char| matches!(c, '#' | '{' | '}' | '<' | '>' | '[' | ']' | '"' | '$');
+    if !name.contains(is_hostile) {
+        output.push_str(name);
+        return;
+    }
+
+    for c in name.chars() {
+        if is_hostile(c) {
+            write!(output, "${:02x}", c as u32).unwrap();
+        } else {
+            output.push(c);
+        }
+    }
+}
+
 fn push_generic_params_internal<'tcx>(
     tcx: TyCtxt<'tcx>,
     substs: SubstsRef<'tcx>,
+    opts: &DebuginfoTypeNameOptions,
+    emitter: &dyn DebuginfoNameEmitter<'tcx>,
     output: &mut String,
-    visited: &mut FxHashSet<Ty<'tcx>>,
+    visited: &mut VisitedTypes<'tcx>,
 ) -> bool {
     if substs.non_erasable_generics().next().is_none() {
         return false;
     }
 
-    debug_assert_eq!(substs, tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), substs));
+    // Only names that include lifetimes are computed for types that still have regions.
+    if !opts.lifetimes {
+        debug_assert_eq!(substs, tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), substs));
+    }
 
-    let cpp_like_debuginfo = cpp_like_debuginfo(tcx);
+    let cpp_like_debuginfo = opts.cpp_like_debuginfo();
 
     output.push('<');
 
     for type_parameter in substs.non_erasable_generics() {
         match type_parameter {
             GenericArgKind::Type(type_parameter) => {
-                push_debuginfo_type_name(tcx, type_parameter, true, output, visited);
+                push_debuginfo_type_name(tcx, type_parameter, true, opts, emitter, output, visited);
             }
             GenericArgKind::Const(ct) => {
-                push_const_param(tcx, ct, output);
+                push_const_param(tcx, ct, opts, output);
             }
             other => bug!("Unexpected non-erasable generic: {:?}", other),
         }
 
-        push_arg_separator(cpp_like_debuginfo, output);
+        push_arg_separator(opts, output);
     }
     pop_arg_separator(output);
     push_close_angle_bracket(cpp_like_debuginfo, output);
@@ -645,97 +2572,530 @@ fn push_generic_params_internal<'tcx>(
     true
 }
 
-fn push_const_param<'tcx>(tcx: TyCtxt<'tcx>, ct: ty::Const<'tcx>, output: &mut String) {
+// The allowlist of library types that get an alias with `opts.aliases`, as the C++-like and
+// the Rust-style name of the alias. Types are recognized by their lang or diagnostic items,
+// so that user-defined types that happen to have the same name keep their full path.
+fn debuginfo_type_alias(tcx: TyCtxt<'_>, def_id: DefId) -> Option<(&'static str, &'static str)> {
+    let lang_items = tcx.lang_items();
+    if Some(def_id) == lang_items.pin_type() {
+        return Some(("pin$", "Pin"));
+    }
+    // Transparent wrappers that debuggers should see through to the wrapped value.
+    if Some(def_id) == lang_items.maybe_uninit() {
+        return Some(("maybe_uninit$", "MaybeUninit"));
+    }
+    if Some(def_id) == lang_items.manually_drop() {
+        return Some(("manually_drop$", "ManuallyDrop"));
+    }
+    // Ranges, so that visualizers can show them like `start..end`. `RangeFull` has nothing
+    // to show.
+    if Some(def_id) == lang_items.range_struct() {
+        return Some(("range$", "Range"));
+    }
+    if Some(def_id) == lang_items.range_from_struct() {
+        return Some(("range_from$", "RangeFrom"));
+    }
+    if Some(def_id) == lang_items.range_inclusive_struct() {
+        return Some(("range_inclusive$", "RangeInclusive"));
+    }
+    if Some(def_id) == lang_items.range_to_struct() {
+        return Some(("range_to$", "RangeTo"));
+    }
+    if Some(def_id) == lang_items.range_to_inclusive_struct() {
+        return Some(("range_to_inclusive$", "RangeToInclusive"));
+    }
+
+    Some(match tcx.get_diagnostic_name(def_id)? {
+        sym::NonZeroU8
+        | sym::NonZeroU16
+        | sym::NonZeroU32
+        | sym::NonZeroU64
+        | sym::NonZeroU128
+        | sym::NonZeroUsize
+        | sym::NonZeroI8
+        | sym::NonZeroI16
+        | sym::NonZeroI32
+        | sym::NonZeroI64
+        | sym::NonZeroI128
+        | sym::NonZeroIsize => ("nonzero$", "NonZero"),
+        // Interior mutability wrappers get an alias so that visualizers can reach through
+        // them to the value (and show the borrow state) without knowing their full path.
+        sym::Cell => ("cell$", "Cell"),
+        sym::RefCell => ("ref_cell$", "RefCell"),
+        sym::Mutex => ("mutex$", "Mutex"),
+        // The same goes for reference-counted pointers and their reference counts.
+        sym::Arc => ("arc$", "Arc"),
+        sym::Rc => ("rc$", "Rc"),
+        // `Discriminant<T>` only stores the tag of `T`, which visualizers can only map to a
+        // variant of `T` if they know that the tag belongs to it.
+        sym::Discriminant => ("discriminant$", "Discriminant"),
+        _ => return None,
+    })
+}
+
+// The allowlist of library types whose path is left out with `opts.short_std_paths`. Like
+// aliases, they are recognized by their lang or diagnostic items rather than by their name.
+fn has_short_std_path(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    if Some(def_id) == tcx.lang_items().owned_box() {
+        return true;
+    }
+
+    matches!(
+        tcx.get_diagnostic_name(def_id),
+        Some(
+            sym::Arc
+                | sym::BTreeMap
+                | sym::BTreeSet
+                | sym::BinaryHeap
+                | sym::Cow
+                | sym::HashMap
+                | sym::HashSet
+                | sym::LinkedList
+                | sym::Mutex
+                | sym::Option
+                | sym::Rc
+                | sym::Result
+                | sym::String
+                | sym::Vec
+                | sym::VecDeque
+        )
+    )
+}
+
+// The generic arguments of the item `def_id` (an ADT or a trait) that go into its name. With
+// `opts.omit_default_args`, trailing arguments that are the same as the defaults of their
+// parameters are left out, like the pretty printer does.
+fn generic_args_to_print<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    substs: SubstsRef<'tcx>,
+    opts: &DebuginfoTypeNameOptions,
+) -> SubstsRef<'tcx> {
+    if !opts.omit_default_args {
+        return substs;
+    }
+
+    let generics = tcx.generics_of(def_id);
+    let defaults = generics
+        .params
+        .iter()
+        .rev()
+        .take_while(|param| match param.kind {
+            ty::GenericParamDefKind::Lifetime => false,
+            ty::GenericParamDefKind::Type { has_default, .. } => {
+                has_default
+                    && substs[param.index as usize]
+                        == GenericArg::from(tcx.type_of(param.def_id).subst(tcx, substs))
+            }
+            ty::GenericParamDefKind::Const { has_default } => {
+                has_default
+                    && substs[param.index as usize]
+                        == GenericArg::from(tcx.const_param_default(param.def_id))
+            }
+        })
+        .count();
+
+    tcx.intern_substs(&substs[..substs.len() - defaults])
+}
+
+// Like generic_args_to_print() but for the generic arguments of a trait without the `Self`
+// type, which the defaults of its parameters can refer to (like in `PartialEq<Rhs = Self>`).
+fn trait_args_to_print<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trait_def_id: DefId,
+    self_ty: Ty<'tcx>,
+    substs: SubstsRef<'tcx>,
+    opts: &DebuginfoTypeNameOptions,
+) -> SubstsRef<'tcx> {
+    if !opts.omit_default_args {
+        return substs;
+    }
+
+    let substs = tcx.mk_substs_trait(self_ty, substs);
+    let substs = generic_args_to_print(tcx, trait_def_id, substs, opts);
+    tcx.intern_substs(&substs[1..])
+}
+
+// The def-ids of `trait_def_id` and of all of its supertraits.
+fn supertrait_def_ids(tcx: TyCtxt<'_>, trait_def_id: DefId) -> FxHashSet<DefId> {
+    let mut def_ids = FxHashSet::default();
+    let mut stack = vec![trait_def_id];
+    while let Some(def_id) = stack.pop() {
+        if def_ids.insert(def_id) {
+            for &(predicate, _) in tcx.super_predicates_of(def_id).predicates {
+                if let ty::PredicateKind::Trait(pred) = predicate.kind().skip_binder() {
+                    stack.push(pred.def_id());
+                }
+            }
+        }
+    }
+    def_ids
+}
+
+// The name of the value of an associated item binding, for ordering bindings.
+fn debuginfo_term_name<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    term: ty::Term<'tcx>,
+    opts: &DebuginfoTypeNameOptions,
+) -> String {
+    match term {
+        ty::Term::Ty(ty) => compute_debuginfo_type_name_with_options(tcx, ty, opts),
+        ty::Term::Const(ct) => {
+            let mut name = String::new();
+            push_const_param(tcx, ct, opts, &mut name);
+            name
+        }
+    }
+}
+
+fn push_const_param<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ct: ty::Const<'tcx>,
+    opts: &DebuginfoTypeNameOptions,
+    output: &mut String,
+) {
     match ct.val() {
         ty::ConstKind::Param(param) => {
-            write!(output, "{}", param.name)
+            // A const parameter can have the same name as a type parameter, e.g. if one of
+            // them comes from a macro with def-site hygiene, so it is marked as such.
+            if opts.cpp_like_debuginfo() {
+                write!(output, "const${}", param.name)
+            } else {
+                write!(output, "const {}", param.name)
+            }
         }
+        // Constant expressions involving generic parameters (e.g. `{ N + 1 }` with
+        // `generic_const_exprs`) can't be evaluated, so they are named by their hash.
+        _ if ct.has_param_types_or_consts() => push_const_hash(tcx, ct, opts, output),
         _ => match ct.ty().kind() {
+            // `eval_bits` returns all 128 bits, so `i128` and `u128` values are printed in full.
             ty::Int(ity) => {
                 let bits = ct.eval_bits(tcx, ty::ParamEnv::reveal_all(), ct.ty());
                 let val = Integer::from_int_ty(&tcx, *ity).size().sign_extend(bits) as i128;
-                write!(output, "{}", val)
+                let suffix = if opts.int_suffixes { ity.name_str() } else { "" };
+                if opts.cpp_like_debuginfo() && val < 0 {
+                    // The MSVC debugger's expression parser would take a leading `-` inside
+                    // of `<...>` for an operator, so negative values are spelled `neg$` plus
+                    // their magnitude instead, e.g. `neg$1` for `-1`. `unsigned_abs` keeps
+                    // `i128::MIN` intact.
+                    write!(output, "neg${}{}", val.unsigned_abs(), suffix)
+                } else {
+                    write!(output, "{}{}", val, suffix)
+                }
             }
-            ty::Uint(_) => {
+            ty::Uint(uty) => {
                 let val = ct.eval_bits(tcx, ty::ParamEnv::reveal_all(), ct.ty());
-                write!(output, "{}", val)
+                let suffix = if opts.int_suffixes { uty.name_str() } else { "" };
+                if opts.hex_consts {
+                    write!(output, "{:#x}{}", val, suffix)
+                } else {
+                    write!(output, "{}{}", val, suffix)
+                }
             }
             ty::Bool => {
                 let val = ct.try_eval_bool(tcx, ty::ParamEnv::reveal_all()).unwrap();
                 write!(output, "{}", val)
             }
-            _ => {
-                // If we cannot evaluate the constant to a known type, we fall back
-                // to emitting a stable hash value of the constant. This isn't very pretty
-                // but we get a deterministic, virtually unique value for the constant.
-                let hcx = &mut tcx.create_stable_hashing_context();
-                let mut hasher = StableHasher::new();
-                hcx.while_hashing_spans(false, |hcx| {
-                    hcx.with_node_id_hashing_mode(NodeIdHashingMode::HashDefPath, |hcx| {
-                        ct.val().hash_stable(hcx, &mut hasher);
-                    });
-                });
-                // Let's only emit 64 bits of the hash value. That should be plenty for
-                // avoiding collisions and will make the emitted type names shorter.
-                let hash: u64 = hasher.finish();
-
-                if cpp_like_debuginfo(tcx) {
-                    write!(output, "CONST${:x}", hash)
+            ty::Float(float_ty) => {
+                let bits = ct.eval_bits(tcx, ty::ParamEnv::reveal_all(), ct.ty());
+                // The `Debug` impls print the shortest representation that parses back to
+                // the same bits, always include a fractional part or exponent (so `1.0`
+                // doesn't look like an integer), keep the sign of negative zero (`-0.0`),
+                // and print non-finite values as `inf`, `-inf`, and `NaN`.
+                let val = match float_ty {
+                    ty::FloatTy::F32 => format!("{:?}", f32::from_bits(bits as u32)),
+                    ty::FloatTy::F64 => format!("{:?}", f64::from_bits(bits as u64)),
+                };
+                if opts.cpp_like_debuginfo() {
+                    // Like negative integers, negative values (including `-0.0`) are spelled
+                    // `neg$` plus their magnitude for the MSVC debugger's expression parser,
+                    // and the non-finite values `inf$` and `nan$` so that they can't be taken
+                    // for identifiers.
+                    let (sign, magnitude) = match val.strip_prefix('-') {
+                        Some(magnitude) => ("neg$", magnitude),
+                        None => ("", &val[..]),
+                    };
+                    let magnitude = match magnitude {
+                        "inf" => "inf$",
+                        "NaN" => "nan$",
+                        magnitude => magnitude,
+                    };
+                    write!(output, "{}{}", sign, magnitude)
+                } else {
+                    output.write_str(&val)
+                }
+            }
+            ty::Char => {
+                let val = ct.eval_bits(tcx, ty::ParamEnv::reveal_all(), ct.ty());
+                if opts.cpp_like_debuginfo() {
+                    // `'` starts a character literal for the MSVC debugger's expression
+                    // parser, so we emit the code point as a plain integer instead.
+                    write!(output, "{}", val)
                 } else {
-                    write!(output, "{{CONST#{:x}}}", hash)
+                    let val = char::from_u32(val as u32).unwrap();
+                    write!(output, "'{}'", val.escape_debug())
                 }
             }
+            // Small arrays and tuples (as allowed by `adt_const_params`) are spelled out,
+            // e.g. `{[1, 2, 3]}` or `const_array$<1,2,3>`, see push_aggregate_const().
+            ty::Array(..) | ty::Tuple(_) => {
+                let mut aggregate = String::new();
+                if !push_aggregate_const(tcx, ct, opts, &mut aggregate, 0) {
+                    push_const_hash(tcx, ct, opts, output)
+                } else if opts.cpp_like_debuginfo() {
+                    output.write_str(&aggregate)
+                } else {
+                    // The braces keep the value apart from array and tuple types.
+                    write!(output, "{{{}}}", aggregate)
+                }
+            }
+            // String slices (as allowed by `adt_const_params`) are spelled out too, e.g.
+            // `{"hi"}` or `const_str$<hi>`, see push_str_const().
+            ty::Ref(_, inner_ty, _) if inner_ty.is_str() => {
+                if !push_str_const(tcx, ct, opts, output) {
+                    push_const_hash(tcx, ct, opts, output)
+                } else {
+                    Ok(())
+                }
+            }
+            // Values of fieldless enums (as allowed by `adt_const_params`) are named by their
+            // variant, e.g. `my_crate::Color::Red`.
+            ty::Adt(adt_def, _) if adt_def.is_enum() && adt_def.is_payloadfree() => {
+                match tcx.try_destructure_const(ty::ParamEnv::reveal_all().and(ct)) {
+                    Some(DestructuredConst { variant: Some(variant), .. }) => {
+                        let variant = adt_def.variant(variant);
+                        push_item_name_internal(tcx, adt_def.did(), true, opts, output);
+                        write!(output, "::{}", variant.name)
+                    }
+                    _ => push_const_hash(tcx, ct, opts, output),
+                }
+            }
+            // If we cannot evaluate the constant to a known type, we fall back
+            // to emitting a stable hash value of the constant.
+            _ => push_const_hash(tcx, ct, opts, output),
         },
     }
     .unwrap();
 }
 
-pub fn push_generic_params<'tcx>(tcx: TyCtxt<'tcx>, substs: SubstsRef<'tcx>, output: &mut String) {
-    let _prof = tcx.prof.generic_activity("compute_debuginfo_type_name");
-    let mut visited = FxHashSet::default();
-    push_generic_params_internal(tcx, substs, output, &mut visited);
+// Pushes the value of an array or tuple constant as `[1, 2, 3]` or `(1, 'x')`, or as
+// `const_array$<1,2,3>` or `const_tuple$<1,120>` in C++-like names. Only arrays and tuples
+// of scalars (and of such arrays and tuples) are spelled out, and only as long as they are
+// small. Returns `false` for all other constants, which leaves `output` in an unspecified
+// state.
+fn push_aggregate_const<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ct: ty::Const<'tcx>,
+    opts: &DebuginfoTypeNameOptions,
+    output: &mut String,
+    depth: usize,
+) -> bool {
+    const MAX_DEPTH: usize = 4;
+    const MAX_FIELDS: usize = 16;
+
+    if depth >= MAX_DEPTH {
+        return false;
+    }
+    let Some(contents) = tcx.try_destructure_const(ty::ParamEnv::reveal_all().and(ct)) else {
+        return false;
+    };
+    if contents.fields.len() > MAX_FIELDS {
+        return false;
+    }
+
+    let cpp_like_debuginfo = opts.cpp_like_debuginfo();
+    let is_array = matches!(ct.ty().kind(), ty::Array(..));
+    output.push_str(match (cpp_like_debuginfo, is_array) {
+        (true, true) => "const_array$<",
+        (true, false) => "const_tuple$<",
+        (false, true) => "[",
+        (false, false) => "(",
+    });
+
+    for (i, &field) in contents.fields.iter().enumerate() {
+        if i > 0 {
+            push_arg_separator(opts, output);
+        }
+        match field.ty().kind() {
+            ty::Int(_) | ty::Uint(_) | ty::Bool | ty::Char | ty::Float(_) => {
+                push_const_param(tcx, field, opts, output)
+            }
+            ty::Array(..) | ty::Tuple(_) => {
+                if !push_aggregate_const(tcx, field, opts, output, depth + 1) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+
+    if cpp_like_debuginfo {
+        push_close_angle_bracket(cpp_like_debuginfo, output);
+    } else if is_array {
+        output.push(']');
+    } else if contents.fields.len() == 1 {
+        output.push_str(",)");
+    } else {
+        output.push(')');
+    }
+
+    true
 }
 
-fn push_close_angle_bracket(cpp_like_debuginfo: bool, output: &mut String) {
-    // MSVC debugger always treats `>>` as a shift, even when parsing templates,
-    // so add a space to avoid confusion.
-    if cpp_like_debuginfo && output.ends_with('>') {
-        output.push(' ')
+// Pushes the value of a `&str` constant as a string literal in braces, e.g. `{"hi\n"}`. In
+// C++-like names, where quotes would start a string, it is pushed as `const_str$<hi$0a>`, with
+// all bytes other than ASCII letters, digits, and `_` escaped like in
+// push_cpp_like_escaped_name(). Long strings are cut off, which is marked by `...` after the
+// closing quote or by a trailing `truncated$` argument. Returns `false` if the value is not
+// known, without pushing anything.
+fn push_str_const<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ct: ty::Const<'tcx>,
+    opts: &DebuginfoTypeNameOptions,
+    output: &mut String,
+) -> bool {
+    const MAX_CHARS: usize = 32;
+
+    let ct = ct.eval(tcx, ty::ParamEnv::reveal_all());
+    let ty::ConstKind::Value(value @ ConstValue::Slice { .. }) = ct.val() else {
+        return false;
+    };
+    let Ok(value) = std::str::from_utf8(get_slice_bytes(&tcx, value)) else {
+        return false;
+    };
+    let (value, truncated) = match value.char_indices().nth(MAX_CHARS) {
+        Some((end, _)) => (&value[..end], true),
+        None => (value, false),
     };
 
-    output.push('>');
+    if opts.cpp_like_debuginfo() {
+        output.push_str("const_str$<");
+        for byte in value.bytes() {
+            if byte.is_ascii_alphanumeric() || byte == b'_' {
+                output.push(byte as char);
+            } else {
+                write!(output, "${:02x}", byte).unwrap();
+            }
+        }
+        if truncated {
+            push_arg_separator(opts, output);
+            output.push_str("truncated$");
+        }
+        push_close_angle_bracket(true, output);
+    } else {
+        write!(output, "{{{:?}{}}}", value, if truncated { "..." } else { "" }).unwrap();
+    }
+    true
 }
 
-fn pop_close_angle_bracket(output: &mut String) {
-    assert!(output.ends_with('>'), "'output' does not end with '>': {}", output);
-    output.pop();
-    if output.ends_with(' ') {
-        output.pop();
+// Pushes the length of an array type, which is a const parameter, the hash of a constant
+// expression involving generic parameters (see push_const_param()), or a plain number.
+fn push_array_len<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    len: ty::Const<'tcx>,
+    opts: &DebuginfoTypeNameOptions,
+    output: &mut String,
+) {
+    match len.val() {
+        ty::ConstKind::Param(param) => write!(output, "{}", param.name),
+        _ if len.has_param_types_or_consts() => push_const_hash(tcx, len, opts, output),
+        _ => write!(output, "{}", len.eval_usize(tcx, ty::ParamEnv::reveal_all())),
     }
+    .unwrap();
 }
 
-fn push_arg_separator(cpp_like_debuginfo: bool, output: &mut String) {
-    // Natvis does not always like having spaces between parts of the type name
-    // and this causes issues when we need to write a typename in natvis, for example
-    // as part of a cast like the `HashMap` visualizer does.
-    if cpp_like_debuginfo {
-        output.push(',');
+// Pushes a stable hash value of `ct` as its name. This isn't very pretty but we get a
+// deterministic, virtually unique value for the constant.
+fn push_const_hash<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ct: ty::Const<'tcx>,
+    opts: &DebuginfoTypeNameOptions,
+    output: &mut String,
+) -> std::fmt::Result {
+    let hcx = &mut tcx.create_stable_hashing_context();
+    let mut hasher = StableHasher::new();
+    hcx.while_hashing_spans(false, |hcx| {
+        hcx.with_node_id_hashing_mode(NodeIdHashingMode::HashDefPath, |hcx| {
+            // The type is part of the hash so that constants of different types don't get
+            // the same name just because their values are encoded the same way.
+            ct.ty().hash_stable(hcx, &mut hasher);
+            ct.val().hash_stable(hcx, &mut hasher);
+        });
+    });
+    // Let's only emit 64 bits of the (128 bit) hash value. That should be plenty
+    // for avoiding collisions and will make the emitted type names shorter.
+    let hash: u64 = hasher.finish::<Fingerprint>().to_smaller_hash();
+
+    if opts.cpp_like_debuginfo() {
+        write!(output, "CONST${:x}", hash)
     } else {
-        output.push_str(", ");
-    };
+        write!(output, "{{CONST#{:x}}}", hash)
+    }
 }
 
-fn pop_arg_separator(output: &mut String) {
-    if output.ends_with(' ') {
-        output.pop();
-    }
+pub fn push_generic_params<'tcx>(tcx: TyCtxt<'tcx>, substs: SubstsRef<'tcx>, output: &mut String) {
+    let _prof = tcx.prof.generic_activity("compute_debuginfo_type_name");
+    let opts = DebuginfoTypeNameOptions::new(tcx, true);
+    let mut visited = VisitedTypes::default();
+    let emitter = &DefaultDebuginfoNameEmitter;
+    push_generic_params_internal(tcx, substs, &opts, emitter, output, &mut visited);
+}
 
-    assert!(output.ends_with(','));
+/// Like [`push_generic_params`], but continues the walk described by `visited`, for callers
+/// that assemble a name from several parts and want recursive types and the depth limit to be
+/// handled as if the name was computed in one go. Each call leaves `visited` in the state it
+/// found it in.
+pub fn push_generic_params_with_visited<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    substs: SubstsRef<'tcx>,
+    output: &mut String,
+    visited: &mut VisitedTypes<'tcx>,
+) {
+    let _prof = tcx.prof.generic_activity("compute_debuginfo_type_name");
+    let opts = DebuginfoTypeNameOptions::new(tcx, true);
+    let emitter = &DefaultDebuginfoNameEmitter;
+    push_generic_params_internal(tcx, substs, &opts, emitter, output, visited);
+}
+
+fn push_arg_separator(opts: &DebuginfoTypeNameOptions, output: &mut String) {
+    if opts.dwarf_safe {
+        output.push(',');
+    } else {
+        separators::push_arg_separator(opts.cpp_like_debuginfo(), opts.spaced_separators, output);
+    }
+}
 
-    output.pop();
+// Pushes the `; ` between the element type and the length of Rust-style array and SIMD
+// vector names, e.g. `[u8; 3]` or `simd<f32; 4>`.
+fn push_len_separator(opts: &DebuginfoTypeNameOptions, output: &mut String) {
+    output.push_str(if opts.dwarf_safe { ";" } else { "; " });
 }
 
 /// Check if we should generate C++ like names and debug information.
 pub fn cpp_like_debuginfo(tcx: TyCtxt<'_>) -> bool {
     tcx.sess.target.is_like_msvc
 }
+
+/// Guesses whether `name` is a C++-like debuginfo type name, for tools that only have the
+/// name at hand and no `TyCtxt`. This is a heuristic, not a parser: it looks for the `$`
+/// that C++-like names use for everything that Rust-style names write in Rust syntax
+/// (`tuple$<...>`, `ref$<...>`, `closure_env$0`, ...), for `> >`, for C++ function
+/// pointers, and for commas that are not followed by a space. Names with quotes (lifetimes
+/// and `char` and `str` constants, which may contain anything) are always Rust-style.
+/// Commas don't count in names that use Rust syntax C++-like names don't have, like the
+/// trailing comma of `(T,)` or the tuples, slices, and references of names computed with
+/// `-Z debuginfo-names-dwarf-safe`. Names that are the same in both styles, like `u8` or
+/// `alloc::string::String`, are reported as Rust-style, except for dwarf-safe names like
+/// `Foo<u8,u16>` that can't be told apart from C++-like names at all.
+pub fn name_is_cpp_like_style(name: &str) -> bool {
+    if name.contains(['\'', '"']) {
+        return false;
+    }
+    if name.contains('$') || name.contains("> >") || name.contains(" (*)(") {
+        return true;
+    }
+    let has_rust_syntax =
+        name.contains(['(', '[', '&', '*', ';']) || name.contains("dyn ") || name.contains("impl ");
+    !has_rust_syntax
+        && name.as_bytes().windows(2).any(|w| w[0] == b',' && w[1] != b' ' && w[1] != b')')
+}
@@ -0,0 +1,734 @@
+//! Parsing of the Rust-style (i.e. non-C++-like) type names produced by
+//! [`compute_debuginfo_type_name`](super::compute_debuginfo_type_name).
+//!
+//! Tools reading DWARF produced by rustc often need a structured view of the type names
+//! stored there. The parser below covers the grammar emitted by `type_names.rs` and has to
+//! be kept in sync with it. It does not try to recover actual `Ty` values, it only builds a
+//! lightweight tree. Printing that tree with `Display` reproduces the original name.
+
+use std::fmt;
+
+/// The structure of a Rust-style debuginfo type name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DebuginfoTypeRepr {
+    /// A (possibly qualified) path with optional generic arguments, e.g.
+    /// `alloc::vec::Vec<u8, alloc::alloc::Global>`, `u32`, or `main::{closure_env#0}`.
+    Path(DebuginfoPath),
+    /// `(A, B)`, `(A,)` for a tuple of one, or `()` for the unit type.
+    Tuple(Vec<DebuginfoTypeRepr>),
+    /// `[T]`
+    Slice(Box<DebuginfoTypeRepr>),
+    /// `[T; N]`, where `N` is kept as written.
+    Array(Box<DebuginfoTypeRepr>, String),
+    /// `simd<T; N>`, the canonical name of SIMD vector types.
+    Simd(Box<DebuginfoTypeRepr>, u64),
+    /// `&T` or `&mut T`, or `&'a T` if `-Z debuginfo-names-lifetimes` is enabled. The
+    /// lifetime is kept including its leading `'`.
+    Ref { lifetime: Option<String>, mutable: bool, pointee: Box<DebuginfoTypeRepr> },
+    /// `*const T` or `*mut T`
+    Ptr { mutable: bool, pointee: Box<DebuginfoTypeRepr> },
+    /// `!`
+    Never,
+    /// `dyn A<B=C> + Send`. The principal trait (if any) comes first, associated type
+    /// bindings are part of its generic arguments. The lifetime bound, as in
+    /// `dyn A + 'a`, is only there if `-Z debuginfo-names-lifetimes` is enabled. A trait
+    /// object without any traits is named `dyn '_`. With
+    /// `-Z debuginfo-names-absent-auto-traits`, the traits are followed by the auto traits
+    /// the object lacks, as in `dyn A + !Send`, which are kept without their `!`.
+    Dyn { traits: Vec<DebuginfoPath>, absent_auto_traits: Vec<String>, lifetime: Option<String> },
+    /// `impl A<B=C> + D`, the name of an opaque type, which is only emitted with
+    /// `-Z debuginfo-names-fallback`.
+    Impl(Vec<DebuginfoPath>),
+    /// `extern type a::B`, the name of a type declared in an `extern` block.
+    ExternType(DebuginfoPath),
+    /// `unsafe extern "C" fn(A, B, ...) -> R`, followed by the path of the function, as in
+    /// `fn(A) -> R {a::f}`, for function items with `-Z debuginfo-names-fn-item-paths`. With
+    /// `-Z debuginfo-names-lifetimes`, higher-ranked signatures can start with a binder like
+    /// `for<'a, 'b> `, whose lifetimes are kept including their leading `'`.
+    FnPtr {
+        bound_lifetimes: Vec<String>,
+        unsafety: bool,
+        abi: Option<String>,
+        inputs: Vec<DebuginfoTypeRepr>,
+        c_variadic: bool,
+        output: Option<Box<DebuginfoTypeRepr>>,
+        item: Option<DebuginfoPath>,
+    },
+    /// The `<recursive_type>` placeholder.
+    Recursive,
+    /// The `<unknown:Kind>` placeholder emitted by `-Z debuginfo-names-fallback`.
+    Unknown(String),
+}
+
+/// A path like `core::option::Option<u8>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebuginfoPath {
+    /// The path segments, e.g. `["core", "option", "Option"]`. Special segments like
+    /// `{closure_env#0}` or `{impl#1}` are kept verbatim, braces included, and so are crate
+    /// disambiguators like in `foo[6e2cc8a1b3c5d7f9]`.
+    pub segments: Vec<String>,
+    /// The generic arguments of the last segment, if any.
+    pub generic_args: Vec<DebuginfoGenericArg>,
+    /// The suffix describing the niche of an enum, e.g. `{niche#1..=255#Some}`, which is only
+    /// emitted with `-Z debuginfo-names-niche-hints`. Kept verbatim, braces included.
+    pub niche_hint: Option<String>,
+    /// The types captured by a closure or generator, e.g. the `(u8, &str)` in
+    /// `{closure_env#0}{captures=(u8, &str)}`, which are only emitted with
+    /// `-Z debuginfo-names-closure-captures`.
+    pub captures: Option<Box<DebuginfoTypeRepr>>,
+    /// The name of the `async fn` a state machine belongs to, e.g. the `foo` in
+    /// `{async_fn_env#0}{fn=foo}`, which is only emitted with
+    /// `-Z debuginfo-names-async-fn-names`.
+    pub async_fn: Option<String>,
+    /// The number of states of a generator or async state machine, e.g. the `5` in
+    /// `{generator_env#0}{states=5}`, which is only emitted with
+    /// `-Z debuginfo-names-generator-states`.
+    pub generator_states: Option<usize>,
+    /// The size and alignment of a struct, enum, or union in bytes, e.g. `(16, 8)` for
+    /// `Foo /*size=16 align=8*/`, which are only emitted with
+    /// `-Z debuginfo-names-layout-hints`.
+    pub layout_hint: Option<(u64, u64)>,
+    /// Whether a struct is marked as unsized, e.g. `Packet{?Sized}`, which is only emitted
+    /// with `-Z debuginfo-names-unsized-hints`.
+    pub unsized_hint: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DebuginfoGenericArg {
+    Type(DebuginfoTypeRepr),
+    /// A const argument that can't be mistaken for a type, e.g. `-7`, `'x'`, `3.14`,
+    /// `{CONST#1d3f}`, `{[1, 2, 3]}`, `{"hi"}`, or the const parameter `const N`. Const
+    /// arguments like `true` look like paths and are parsed as [`DebuginfoGenericArg::Type`].
+    Const(String),
+    /// An associated type binding in a trait object, e.g. `Item=u8`.
+    Binding(String, DebuginfoTypeRepr),
+}
+
+/// The error returned by [`parse_debuginfo_type_name`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The byte offset into the input at which parsing failed.
+    pub position: usize,
+    /// A description of what the parser expected at `position`.
+    pub expected: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {} at byte {}", self.expected, self.position)
+    }
+}
+
+/// Parses a type name produced by `compute_debuginfo_type_name` for a target that does not
+/// use C++-like debuginfo.
+pub fn parse_debuginfo_type_name(name: &str) -> Result<DebuginfoTypeRepr, ParseError> {
+    let mut parser = Parser { input: name, pos: 0 };
+    let repr = parser.parse_type()?;
+    if parser.pos != name.len() {
+        return Err(parser.error("end of type name"));
+    }
+    Ok(repr)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn error(&self, expected: &'static str) -> ParseError {
+        ParseError { position: self.pos, expected }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &'static str) -> Result<(), ParseError> {
+        if self.eat(token) { Ok(()) } else { Err(self.error(token)) }
+    }
+
+    fn parse_type(&mut self) -> Result<DebuginfoTypeRepr, ParseError> {
+        if self.eat("!") {
+            return Ok(DebuginfoTypeRepr::Never);
+        }
+        if self.eat("<recursive_type>") {
+            return Ok(DebuginfoTypeRepr::Recursive);
+        }
+        if self.eat("<unknown:") {
+            let kind = self.take_while(|c| c != '>');
+            self.expect(">")?;
+            return Ok(DebuginfoTypeRepr::Unknown(kind.to_owned()));
+        }
+        if self.eat("&") {
+            let lifetime = if self.rest().starts_with('\'') {
+                let lifetime = self.parse_lifetime();
+                self.expect(" ")?;
+                Some(lifetime)
+            } else {
+                None
+            };
+            let mutable = self.eat("mut ");
+            let pointee = Box::new(self.parse_type()?);
+            return Ok(DebuginfoTypeRepr::Ref { lifetime, mutable, pointee });
+        }
+        if self.eat("*") {
+            let mutable = if self.eat("mut ") {
+                true
+            } else {
+                self.expect("const ")?;
+                false
+            };
+            let pointee = Box::new(self.parse_type()?);
+            return Ok(DebuginfoTypeRepr::Ptr { mutable, pointee });
+        }
+        if self.eat("[") {
+            let element = Box::new(self.parse_type()?);
+            if self.eat("; ") {
+                let len = self.take_while(|c| c != ']');
+                self.expect("]")?;
+                return Ok(DebuginfoTypeRepr::Array(element, len.to_owned()));
+            }
+            self.expect("]")?;
+            return Ok(DebuginfoTypeRepr::Slice(element));
+        }
+        if self.rest().starts_with("(") {
+            return self.parse_parenthesized();
+        }
+        if self.eat("dyn ") {
+            return self.parse_dyn();
+        }
+        if self.eat("impl ") {
+            return Ok(DebuginfoTypeRepr::Impl(self.parse_impl()?));
+        }
+        if self.eat("extern type ") {
+            return Ok(DebuginfoTypeRepr::ExternType(self.parse_path()?));
+        }
+        if self.rest().starts_with("simd<") {
+            let start = self.pos;
+            self.pos += "simd<".len();
+            let element = Box::new(self.parse_type()?);
+            if self.eat("; ") {
+                let lanes = self.take_while(|c| c.is_ascii_digit());
+                let lanes = lanes.parse().map_err(|_| self.error("lane count"))?;
+                self.expect(">")?;
+                return Ok(DebuginfoTypeRepr::Simd(element, lanes));
+            }
+            // A type that just happens to be called `simd`.
+            self.pos = start;
+        }
+        if self.rest().starts_with("for<")
+            || self.rest().starts_with("unsafe ")
+            || self.rest().starts_with("extern \"")
+            || self.rest().starts_with("fn(")
+        {
+            return self.parse_fn_ptr();
+        }
+        Ok(DebuginfoTypeRepr::Path(self.parse_path()?))
+    }
+
+    // Either a tuple, a trait object with a lifetime bound, or an opaque type with more than
+    // one bound. The emitter wraps the latter two in parentheses.
+    fn parse_parenthesized(&mut self) -> Result<DebuginfoTypeRepr, ParseError> {
+        let start = self.pos;
+        self.expect("(")?;
+        if self.eat("dyn ") {
+            if let DebuginfoTypeRepr::Dyn { traits, absent_auto_traits, lifetime } =
+                self.parse_dyn()?
+            {
+                if lifetime.is_some() && self.eat(")") {
+                    return Ok(DebuginfoTypeRepr::Dyn { traits, absent_auto_traits, lifetime });
+                }
+            }
+            // A tuple starting with a trait object, so start over.
+            self.pos = start + 1;
+        }
+        if self.eat("impl ") {
+            let traits = self.parse_impl()?;
+            if traits.len() > 1 && self.eat(")") {
+                return Ok(DebuginfoTypeRepr::Impl(traits));
+            }
+            self.pos = start + 1;
+        }
+
+        let mut components = Vec::new();
+        while !self.eat(")") {
+            if !components.is_empty() {
+                self.expect(", ")?;
+            }
+            components.push(self.parse_type()?);
+            // A tuple of one has a trailing comma, like in Rust.
+            if components.len() == 1 {
+                if self.eat(",)") {
+                    return Ok(DebuginfoTypeRepr::Tuple(components));
+                }
+                if self.rest().starts_with(')') {
+                    return Err(self.error(",)"));
+                }
+            }
+        }
+        Ok(DebuginfoTypeRepr::Tuple(components))
+    }
+
+    fn parse_dyn(&mut self) -> Result<DebuginfoTypeRepr, ParseError> {
+        // A trait object without any traits, like `dyn '_`.
+        if self.rest().starts_with('\'') {
+            let lifetime = Some(self.parse_lifetime());
+            let absent_auto_traits = Vec::new();
+            return Ok(DebuginfoTypeRepr::Dyn { traits: Vec::new(), absent_auto_traits, lifetime });
+        }
+        let mut traits = vec![self.parse_path()?];
+        let mut absent_auto_traits = Vec::new();
+        let mut lifetime = None;
+        while self.eat(" + ") {
+            // The lifetime bound always comes last.
+            if self.rest().starts_with('\'') {
+                lifetime = Some(self.parse_lifetime());
+                break;
+            }
+            if self.eat("!") {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                absent_auto_traits.push(name.to_owned());
+            } else {
+                traits.push(self.parse_path()?);
+            }
+        }
+        Ok(DebuginfoTypeRepr::Dyn { traits, absent_auto_traits, lifetime })
+    }
+
+    fn parse_impl(&mut self) -> Result<Vec<DebuginfoPath>, ParseError> {
+        let mut traits = vec![self.parse_path()?];
+        while self.eat(" + ") {
+            traits.push(self.parse_path()?);
+        }
+        Ok(traits)
+    }
+
+    // A lifetime like `'a` or `'static`, including the leading `'`.
+    fn parse_lifetime(&mut self) -> String {
+        let start = self.pos;
+        self.pos += 1;
+        self.take_while(|c| c.is_alphanumeric() || c == '_');
+        self.input[start..self.pos].to_owned()
+    }
+
+    fn parse_fn_ptr(&mut self) -> Result<DebuginfoTypeRepr, ParseError> {
+        let mut bound_lifetimes = Vec::new();
+        if self.eat("for<") {
+            loop {
+                if !self.rest().starts_with('\'') {
+                    return Err(self.error("lifetime"));
+                }
+                bound_lifetimes.push(self.parse_lifetime());
+                if self.eat("> ") {
+                    break;
+                }
+                self.expect(", ")?;
+            }
+        }
+        let unsafety = self.eat("unsafe ");
+        let abi = if self.eat("extern \"") {
+            let abi = self.take_while(|c| c != '"');
+            self.expect("\" ")?;
+            Some(abi.to_owned())
+        } else {
+            None
+        };
+        self.expect("fn(")?;
+
+        let mut inputs = Vec::new();
+        let mut c_variadic = false;
+        while !self.eat(")") {
+            if !inputs.is_empty() || c_variadic {
+                self.expect(", ")?;
+            }
+            if self.eat("...") {
+                c_variadic = true;
+            } else {
+                inputs.push(self.parse_type()?);
+            }
+        }
+
+        let output = if self.eat(" -> ") { Some(Box::new(self.parse_type()?)) } else { None };
+        let item = if self.eat(" {") {
+            let item = self.parse_path()?;
+            self.expect("}")?;
+            Some(item)
+        } else {
+            None
+        };
+        Ok(DebuginfoTypeRepr::FnPtr {
+            bound_lifetimes,
+            unsafety,
+            abi,
+            inputs,
+            c_variadic,
+            output,
+            item,
+        })
+    }
+
+    fn parse_path(&mut self) -> Result<DebuginfoPath, ParseError> {
+        let mut segments = vec![self.parse_segment()?];
+        while self.eat("::") {
+            segments.push(self.parse_segment()?);
+        }
+
+        let mut generic_args = Vec::new();
+        if self.eat("<") {
+            while !self.eat(">") {
+                if !generic_args.is_empty() {
+                    self.expect(", ")?;
+                }
+                generic_args.push(self.parse_generic_arg()?);
+            }
+        }
+        let mut async_fn = None;
+        if self.eat("{fn=") {
+            let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+            if name.is_empty() {
+                return Err(self.error("function name"));
+            }
+            async_fn = Some(name.to_owned());
+            self.expect("}")?;
+        }
+
+        let mut generator_states = None;
+        if self.eat("{states=") {
+            let states = self.take_while(|c| c.is_ascii_digit());
+            generator_states = Some(states.parse().map_err(|_| self.error("state count"))?);
+            self.expect("}")?;
+        }
+
+        let mut captures = None;
+        if self.eat("{captures=") {
+            captures = Some(Box::new(self.parse_type()?));
+            self.expect("}")?;
+        }
+
+        let mut niche_hint = None;
+        if self.rest().starts_with("{niche#") {
+            let len = self.rest().find('}').ok_or_else(|| self.error("}"))? + 1;
+            niche_hint = Some(self.rest()[..len].to_owned());
+            self.pos += len;
+        }
+
+        let unsized_hint = self.eat("{?Sized}");
+
+        let mut layout_hint = None;
+        if self.eat(" /*size=") {
+            let size = self.take_while(|c| c.is_ascii_digit());
+            let size = size.parse().map_err(|_| self.error("size"))?;
+            self.expect(" align=")?;
+            let align = self.take_while(|c| c.is_ascii_digit());
+            let align = align.parse().map_err(|_| self.error("alignment"))?;
+            self.expect("*/")?;
+            layout_hint = Some((size, align));
+        }
+        Ok(DebuginfoPath {
+            segments,
+            generic_args,
+            niche_hint,
+            captures,
+            async_fn,
+            generator_states,
+            layout_hint,
+            unsized_hint,
+        })
+    }
+
+    fn parse_segment(&mut self) -> Result<String, ParseError> {
+        let segment = if self.rest().starts_with('{') {
+            let len = self.rest().find('}').ok_or_else(|| self.error("}"))? + 1;
+            let segment = &self.rest()[..len];
+            self.pos += len;
+            segment
+        } else {
+            let start = self.pos;
+            self.take_while(|c| c.is_alphanumeric() || c == '_');
+            // The disambiguator of a crate root, see `-Z debuginfo-names-crate-disambiguators`.
+            if self.pos > start && self.eat("[") {
+                self.take_while(|c| c.is_ascii_hexdigit());
+                self.expect("]")?;
+            }
+            &self.input[start..self.pos]
+        };
+
+        if segment.is_empty() { Err(self.error("path segment")) } else { Ok(segment.to_owned()) }
+    }
+
+    fn parse_generic_arg(&mut self) -> Result<DebuginfoGenericArg, ParseError> {
+        let rest = self.rest();
+        if rest.starts_with("{CONST#") {
+            let len = rest.find('}').ok_or_else(|| self.error("}"))? + 1;
+            self.pos += len;
+            return Ok(DebuginfoGenericArg::Const(rest[..len].to_owned()));
+        }
+        if rest.starts_with('\'') {
+            return self.parse_char_const();
+        }
+        if rest.starts_with("{\"") {
+            return self.parse_str_const();
+        }
+        if rest.starts_with("{[") || rest.starts_with("{(") {
+            return self.parse_aggregate_const();
+        }
+        if rest.starts_with("const ") {
+            self.pos += "const ".len();
+            let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+            if name.is_empty() {
+                return Err(self.error("const parameter name"));
+            }
+            return Ok(DebuginfoGenericArg::Const(format!("const {}", name)));
+        }
+        if rest.starts_with(|c: char| c == '-' || c.is_ascii_digit()) {
+            let value =
+                self.take_while(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '+');
+            return Ok(DebuginfoGenericArg::Const(value.to_owned()));
+        }
+
+        // `Name=Type` bindings only appear in trait objects.
+        let start = self.pos;
+        let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+        if !name.is_empty() && self.eat("=") {
+            return Ok(DebuginfoGenericArg::Binding(name.to_owned(), self.parse_type()?));
+        }
+        self.pos = start;
+
+        Ok(DebuginfoGenericArg::Type(self.parse_type()?))
+    }
+
+    fn parse_char_const(&mut self) -> Result<DebuginfoGenericArg, ParseError> {
+        let start = self.pos;
+        self.expect("'")?;
+        let mut escaped = false;
+        for (offset, c) in self.rest().char_indices() {
+            match c {
+                '\\' if !escaped => escaped = true,
+                '\'' if !escaped => {
+                    self.pos += offset + 1;
+                    return Ok(DebuginfoGenericArg::Const(self.input[start..self.pos].to_owned()));
+                }
+                _ => escaped = false,
+            }
+        }
+        Err(self.error("'"))
+    }
+
+    fn parse_str_const(&mut self) -> Result<DebuginfoGenericArg, ParseError> {
+        let start = self.pos;
+        self.expect("{\"")?;
+        let mut escaped = false;
+        let len = self
+            .rest()
+            .char_indices()
+            .find_map(|(offset, c)| match c {
+                '\\' if !escaped => {
+                    escaped = true;
+                    None
+                }
+                '"' if !escaped => Some(offset + 1),
+                _ => {
+                    escaped = false;
+                    None
+                }
+            })
+            .ok_or_else(|| self.error("\""))?;
+        self.pos += len;
+        // Long strings are cut off, which is marked by `...` after the closing quote.
+        self.eat("...");
+        self.expect("}")?;
+        Ok(DebuginfoGenericArg::Const(self.input[start..self.pos].to_owned()))
+    }
+
+    fn parse_aggregate_const(&mut self) -> Result<DebuginfoGenericArg, ParseError> {
+        let start = self.pos;
+        self.expect("{")?;
+        // The elements are scalars, so the first `}` outside of a character constant is the
+        // end of the value.
+        loop {
+            match self.rest().chars().next() {
+                Some('\'') => {
+                    self.parse_char_const()?;
+                }
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(DebuginfoGenericArg::Const(self.input[start..self.pos].to_owned()));
+                }
+                Some(c) => self.pos += c.len_utf8(),
+                None => return Err(self.error("}")),
+            }
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+}
+
+impl fmt::Display for DebuginfoTypeRepr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DebuginfoTypeRepr::Path(path) => write!(f, "{}", path),
+            DebuginfoTypeRepr::Tuple(components) => {
+                f.write_str("(")?;
+                write_comma_separated(f, components)?;
+                f.write_str(if components.len() == 1 { ",)" } else { ")" })
+            }
+            DebuginfoTypeRepr::Slice(element) => write!(f, "[{}]", element),
+            DebuginfoTypeRepr::Array(element, len) => write!(f, "[{}; {}]", element, len),
+            DebuginfoTypeRepr::Simd(element, lanes) => write!(f, "simd<{}; {}>", element, lanes),
+            DebuginfoTypeRepr::Ref { lifetime, mutable, pointee } => {
+                f.write_str("&")?;
+                if let Some(lifetime) = lifetime {
+                    write!(f, "{} ", lifetime)?;
+                }
+                write!(f, "{}{}", if *mutable { "mut " } else { "" }, pointee)
+            }
+            DebuginfoTypeRepr::Ptr { mutable, pointee } => {
+                write!(f, "*{}{}", if *mutable { "mut " } else { "const " }, pointee)
+            }
+            DebuginfoTypeRepr::Never => f.write_str("!"),
+            DebuginfoTypeRepr::Dyn { traits, absent_auto_traits, lifetime } => {
+                // The emitter only adds parentheses if there is a lifetime bound in addition
+                // to the traits.
+                let parenthesized = lifetime.is_some() && !traits.is_empty();
+                if parenthesized {
+                    f.write_str("(")?;
+                }
+                f.write_str("dyn ")?;
+                for (i, trait_path) in traits.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" + ")?;
+                    }
+                    write!(f, "{}", trait_path)?;
+                }
+                for name in absent_auto_traits {
+                    write!(f, " + !{}", name)?;
+                }
+                if let Some(lifetime) = lifetime {
+                    let separator = if traits.is_empty() { "" } else { " + " };
+                    write!(f, "{}{}", separator, lifetime)?;
+                }
+                if parenthesized {
+                    f.write_str(")")?;
+                }
+                Ok(())
+            }
+            DebuginfoTypeRepr::ExternType(path) => write!(f, "extern type {}", path),
+            DebuginfoTypeRepr::Impl(traits) => {
+                // Like trait objects, only parenthesized if there is more than one bound.
+                if traits.len() > 1 {
+                    f.write_str("(")?;
+                }
+                f.write_str("impl ")?;
+                for (i, trait_path) in traits.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" + ")?;
+                    }
+                    write!(f, "{}", trait_path)?;
+                }
+                if traits.len() > 1 {
+                    f.write_str(")")?;
+                }
+                Ok(())
+            }
+            DebuginfoTypeRepr::FnPtr {
+                bound_lifetimes,
+                unsafety,
+                abi,
+                inputs,
+                c_variadic,
+                output,
+                item,
+            } => {
+                if !bound_lifetimes.is_empty() {
+                    write!(f, "for<{}> ", bound_lifetimes.join(", "))?;
+                }
+                if *unsafety {
+                    f.write_str("unsafe ")?;
+                }
+                if let Some(abi) = abi {
+                    write!(f, "extern \"{}\" ", abi)?;
+                }
+                f.write_str("fn(")?;
+                write_comma_separated(f, inputs)?;
+                if *c_variadic {
+                    f.write_str(if inputs.is_empty() { "..." } else { ", ..." })?;
+                }
+                f.write_str(")")?;
+                if let Some(output) = output {
+                    write!(f, " -> {}", output)?;
+                }
+                if let Some(item) = item {
+                    write!(f, " {{{}}}", item)?;
+                }
+                Ok(())
+            }
+            DebuginfoTypeRepr::Recursive => f.write_str("<recursive_type>"),
+            DebuginfoTypeRepr::Unknown(kind) => write!(f, "<unknown:{}>", kind),
+        }
+    }
+}
+
+impl fmt::Display for DebuginfoPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.segments.join("::"))?;
+        if !self.generic_args.is_empty() {
+            f.write_str("<")?;
+            write_comma_separated(f, &self.generic_args)?;
+            f.write_str(">")?;
+        }
+        if let Some(async_fn) = &self.async_fn {
+            write!(f, "{{fn={}}}", async_fn)?;
+        }
+        if let Some(generator_states) = self.generator_states {
+            write!(f, "{{states={}}}", generator_states)?;
+        }
+        if let Some(captures) = &self.captures {
+            write!(f, "{{captures={}}}", captures)?;
+        }
+        if let Some(niche_hint) = &self.niche_hint {
+            f.write_str(niche_hint)?;
+        }
+        if self.unsized_hint {
+            f.write_str("{?Sized}")?;
+        }
+        if let Some((size, align)) = self.layout_hint {
+            write!(f, " /*size={} align={}*/", size, align)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DebuginfoGenericArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DebuginfoGenericArg::Type(ty) => write!(f, "{}", ty),
+            DebuginfoGenericArg::Const(value) => f.write_str(value),
+            DebuginfoGenericArg::Binding(name, ty) => write!(f, "{}={}", name, ty),
+        }
+    }
+}
+
+fn write_comma_separated<T: fmt::Display>(f: &mut fmt::Formatter<'_>, items: &[T]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
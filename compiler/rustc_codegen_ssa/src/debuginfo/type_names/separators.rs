@@ -0,0 +1,90 @@
+//! The pieces of debuginfo type names that don't depend on the types being named: the
+//! separators between generic arguments and trait bounds, and the closing angle brackets.
+//!
+//! These only need the buffer the name is built in, not the type context or the session, so
+//! tools that assemble names of their own (e.g. for `no_std` targets) can produce exactly the
+//! same sequences as the compiler.
+
+/// The separator between the principal trait and the auto traits of a trait object in
+/// Rust-style names, as in `dyn Trait + Send`.
+pub const AUTO_TRAIT_SEPARATOR: &str = " + ";
+
+/// The separator between the traits of a trait object in Rust-style names without spaces
+/// in separators (`-Z debuginfo-names-dwarf-safe`), as in `dyn Trait+Send`.
+pub const COMPACT_AUTO_TRAIT_SEPARATOR: &str = "+";
+
+/// Pushes a `>`. In C++-like names, a space goes before it if the name already ends with a
+/// `>`, since the MSVC debugger always treats `>>` as a shift, even when parsing templates.
+pub fn push_close_angle_bracket(cpp_like_debuginfo: bool, output: &mut String) {
+    // Since this is the only place where `>` gets appended right after another `>`, checking
+    // the last character is sufficient.
+    if cpp_like_debuginfo && output.ends_with('>') {
+        output.push(' ')
+    };
+
+    output.push('>');
+}
+
+/// Removes a `>` pushed by [`push_close_angle_bracket`], including the space before it.
+pub fn pop_close_angle_bracket(output: &mut String) {
+    assert!(output.ends_with('>'), "'output' does not end with '>': {}", output);
+    output.pop();
+    if output.ends_with(' ') {
+        output.pop();
+    }
+}
+
+/// Pushes the separator between generic arguments, tuple components, etc.: `, ` in
+/// Rust-style names, and `,` in C++-like names unless `spaced_separators` is set.
+pub fn push_arg_separator(cpp_like_debuginfo: bool, spaced_separators: bool, output: &mut String) {
+    // Natvis does not always like having spaces between parts of the type name
+    // and this causes issues when we need to write a typename in natvis, for example
+    // as part of a cast like the `HashMap` visualizer does. Visualizers that are
+    // written for it can opt into spaces with `-Z debuginfo-names-spaced-separators`.
+    if cpp_like_debuginfo && !spaced_separators {
+        output.push(',');
+    } else {
+        output.push_str(", ");
+    };
+}
+
+/// Removes a separator pushed by [`push_arg_separator`]. Leaves `output` alone if it doesn't
+/// end with a separator, which is a bug that only debug builds check for.
+pub fn pop_arg_separator(output: &mut String) {
+    let separator_len = if output.ends_with(", ") {
+        2
+    } else if output.ends_with(',') {
+        1
+    } else {
+        0
+    };
+    // A slightly wrong name is better than a crash in a release build of the compiler.
+    debug_assert!(separator_len > 0, "'output' does not end with a separator: {}", output);
+    output.truncate(output.len() - separator_len);
+}
+
+/// Pushes the separator between the traits of a trait object: [`AUTO_TRAIT_SEPARATOR`] in
+/// Rust-style names, and the regular argument separator in C++-like names.
+pub fn push_auto_trait_separator(
+    cpp_like_debuginfo: bool,
+    spaced_separators: bool,
+    output: &mut String,
+) {
+    if cpp_like_debuginfo {
+        push_arg_separator(cpp_like_debuginfo, spaced_separators, output);
+    } else {
+        output.push_str(AUTO_TRAIT_SEPARATOR);
+    }
+}
+
+/// Removes a separator pushed by [`push_auto_trait_separator`]. Like [`pop_arg_separator`],
+/// this leaves `output` alone if it doesn't end with a separator.
+pub fn pop_auto_trait_separator(output: &mut String) {
+    if output.ends_with(AUTO_TRAIT_SEPARATOR) {
+        output.truncate(output.len() - AUTO_TRAIT_SEPARATOR.len());
+    } else if output.ends_with(COMPACT_AUTO_TRAIT_SEPARATOR) {
+        output.truncate(output.len() - COMPACT_AUTO_TRAIT_SEPARATOR.len());
+    } else {
+        pop_arg_separator(output);
+    }
+}
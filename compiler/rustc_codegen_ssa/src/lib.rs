@@ -166,6 +166,7 @@ pub struct CodegenResults {
 pub fn provide(providers: &mut Providers) {
     crate::back::symbol_export::provide(providers);
     crate::base::provide(providers);
+    crate::debuginfo::type_names::provide(providers);
     crate::target_features::provide(providers);
 }
 
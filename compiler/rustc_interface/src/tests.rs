@@ -702,6 +702,7 @@ macro_rules! untracked {
     untracked!(unstable_options, true);
     untracked!(validate_mir, true);
     untracked!(verbose, true);
+    untracked!(verify_debuginfo_type_names, true);
 
     macro_rules! tracked {
         ($name: ident, $non_default_value: expr) => {
@@ -731,6 +732,35 @@ macro_rules! tracked {
     tracked!(crate_attr, vec!["abc".to_string()]);
     tracked!(debug_info_for_profiling, true);
     tracked!(debug_macros, true);
+    tracked!(debuginfo_names_absent_auto_traits, true);
+    tracked!(debuginfo_names_aliases, true);
+    tracked!(debuginfo_names_async_fn_names, true);
+    tracked!(debuginfo_names_canonical, true);
+    tracked!(debuginfo_names_closure_captures, true);
+    tracked!(debuginfo_names_closure_layouts, true);
+    tracked!(debuginfo_names_crate_disambiguators, true);
+    tracked!(debuginfo_names_dwarf_safe, true);
+    tracked!(debuginfo_names_fallback, true);
+    tracked!(debuginfo_names_fat_ptr_hints, true);
+    tracked!(debuginfo_names_fn_item_paths, true);
+    tracked!(debuginfo_names_gdb_friendly, true);
+    tracked!(debuginfo_names_generator_states, true);
+    tracked!(debuginfo_names_hex_consts, true);
+    tracked!(debuginfo_names_int_suffixes, true);
+    tracked!(debuginfo_names_layout_hints, true);
+    tracked!(debuginfo_names_lifetimes, true);
+    tracked!(debuginfo_names_max_depth, 10);
+    tracked!(debuginfo_names_max_len, Some(1024));
+    tracked!(debuginfo_names_niche_hints, true);
+    tracked!(debuginfo_names_omit_default_args, true);
+    tracked!(debuginfo_names_reexport_paths, true);
+    tracked!(debuginfo_names_short_markers, true);
+    tracked!(debuginfo_names_short_std_paths, true);
+    tracked!(debuginfo_names_spaced_separators, true);
+    tracked!(debuginfo_names_unit_void, true);
+    tracked!(debuginfo_names_unsized_hints, true);
+    tracked!(debuginfo_names_wrap_slice_refs, true);
+    tracked!(debuginfo_names_wrap_str_refs, true);
     tracked!(dep_info_omit_d_target, true);
     tracked!(drop_tracking, true);
     tracked!(dual_proc_macros, true);
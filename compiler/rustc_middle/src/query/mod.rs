@@ -999,6 +999,14 @@
         cache_on_disk_if { true }
     }
 
+    /// The `debuginfo_type_name` query computes the name of a type as it is stored in
+    /// debuginfo. The `bool` in the key is the `qualified` flag of
+    /// `rustc_codegen_ssa::debuginfo::type_names::compute_debuginfo_type_name`, since
+    /// qualification of the outermost path differs between the two forms.
+    query debuginfo_type_name(key: (Ty<'tcx>, bool)) -> Symbol {
+        desc { "computing the debuginfo type name of `{}`", key.0 }
+    }
+
     query opt_def_kind(def_id: DefId) -> Option<DefKind> {
         desc { |tcx| "looking up definition kind of `{}`", tcx.def_path_str(def_id) }
         separate_provide_extern
@@ -83,6 +83,17 @@ fn default_span(&self, _: TyCtxt<'_>) -> Span {
     }
 }
 
+impl<'tcx> Key for (Ty<'tcx>, bool) {
+    #[inline(always)]
+    fn query_crate_is_local(&self) -> bool {
+        true
+    }
+
+    fn default_span(&self, _: TyCtxt<'_>) -> Span {
+        DUMMY_SP
+    }
+}
+
 impl<'tcx> Key for mir::interpret::LitToConstInput<'tcx> {
     #[inline(always)]
     fn query_crate_is_local(&self) -> bool {
@@ -1174,6 +1174,93 @@ mod parse {
         "emit discriminators and other data necessary for AutoFDO"),
     debug_macros: bool = (false, parse_bool, [TRACKED],
         "emit line numbers debug info inside macros (default: no)"),
+    debuginfo_names_absent_auto_traits: bool = (false, parse_bool, [TRACKED],
+        "mark trait objects that are not `Send` or `Sync` as such in debuginfo type names, e.g. \
+        `dyn Trait + !Send` (default: no)"),
+    debuginfo_names_aliases: bool = (false, parse_bool, [TRACKED],
+        "name well-known library newtypes like `NonZeroU32`, `Pin`, `RefCell`, and `Rc` by \
+        short aliases in debuginfo type names, e.g. `NonZero<u32>` (default: no)"),
+    debuginfo_names_async_fn_names: bool = (false, parse_bool, [TRACKED],
+        "append the name of the `async fn` to the debuginfo type names of its state machine \
+        (default: no)"),
+    debuginfo_names_canonical: bool = (false, parse_bool, [TRACKED],
+        "spell debuginfo type names canonically wherever the order of their parts is not \
+        determined by the type itself, e.g. for associated type bindings (default: no)"),
+    debuginfo_names_closure_captures: bool = (false, parse_bool, [TRACKED],
+        "append the types captured by closures and generators to their debuginfo type names \
+        (default: no)"),
+    debuginfo_names_closure_layouts: bool = (false, parse_bool, [TRACKED],
+        "name closures after the types of their captures in the order they are laid out in \
+        memory in debuginfo type names, e.g. `{closure_env#0}<u32, u8>` (default: no)"),
+    debuginfo_names_crate_disambiguators: bool = (false, parse_bool, [TRACKED],
+        "append the stable crate id to crate names in debuginfo type names, so that crates \
+        with the same name can be told apart (default: no)"),
+    debuginfo_names_dwarf_safe: bool = (false, parse_bool, [TRACKED],
+        "leave out the spaces in the separators of debuginfo type names, e.g. \
+        `Vec<(u8,u16),Global>` (default: no)"),
+    debuginfo_names_fallback: bool = (false, parse_bool, [TRACKED],
+        "emit a placeholder debuginfo type name for types that are not expected during codegen \
+        instead of reporting an internal compiler error (default: no)"),
+    debuginfo_names_fat_ptr_hints: bool = (false, parse_bool, [TRACKED],
+        "mark raw pointers to unsized types as fat in MSVC-style debuginfo type names, e.g. \
+        `ptr_const$<dyn$<Trait>,fat>` (default: no)"),
+    debuginfo_names_fn_item_paths: bool = (false, parse_bool, [TRACKED],
+        "include the path of the function in the debuginfo type names of function items \
+        (default: no)"),
+    debuginfo_names_gdb_friendly: bool = (false, parse_bool, [TRACKED],
+        "name arrays and slices `array<T, N>` and `slice<T>` in debuginfo type names, which \
+        GDB's expression parser can deal with (default: no)"),
+    debuginfo_names_generator_states: bool = (false, parse_bool, [TRACKED],
+        "append the number of states of generators and async state machines to their \
+        debuginfo type names, e.g. `{generator_env#0}{states=5}` (default: no)"),
+    debuginfo_names_hex_consts: bool = (false, parse_bool, [TRACKED],
+        "name unsigned integer const arguments in hexadecimal in debuginfo type names, e.g. \
+        `Flags<0xff00>` (default: no)"),
+    debuginfo_names_int_suffixes: bool = (false, parse_bool, [TRACKED],
+        "append the type to integer const arguments in debuginfo type names, e.g. `5u8` \
+        (default: no)"),
+    debuginfo_names_layout_hints: bool = (false, parse_bool, [TRACKED],
+        "append the size and alignment of structs, enums, and unions to their debuginfo type \
+        names, e.g. `Foo /*size=16 align=8*/` (default: no)"),
+    debuginfo_names_lifetimes: bool = (false, parse_bool, [TRACKED],
+        "include named lifetimes of references, trait objects, and higher-ranked function \
+        pointers in debuginfo type names, as far as they have not been erased yet (default: no)"),
+    debuginfo_names_max_depth: usize = (1000, parse_number, [TRACKED],
+        "replace types nested deeper than this in debuginfo type names by a placeholder \
+        (default: 1000)"),
+    debuginfo_names_max_len: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "shorten debuginfo type names longer than this many bytes, keeping them unique \
+        by appending a hash of the full name (default: no limit)"),
+    debuginfo_names_niche_hints: bool = (false, parse_bool, [TRACKED],
+        "append the range of niche values and the dataful variant to the Rust-style debuginfo \
+        type names of enums that store their discriminant in a niche (default: no)"),
+    debuginfo_names_omit_default_args: bool = (false, parse_bool, [TRACKED],
+        "leave out trailing generic arguments that are the same as the defaults of their \
+        parameters in debuginfo type names, e.g. the allocator of `Box<T>` (default: no)"),
+    debuginfo_names_reexport_paths: bool = (false, parse_bool, [TRACKED],
+        "name items of other crates by the public path they are re-exported under in \
+        debuginfo type names, e.g. `std::collections::HashMap` (default: no)"),
+    debuginfo_names_short_markers: bool = (false, parse_bool, [TRACKED],
+        "emit well-known marker types like `PhantomData` without their path in debuginfo \
+        type names (default: no)"),
+    debuginfo_names_short_std_paths: bool = (false, parse_bool, [TRACKED],
+        "emit the most common standard library types like `Vec` and `HashMap` without their \
+        path in debuginfo type names (default: no)"),
+    debuginfo_names_spaced_separators: bool = (false, parse_bool, [TRACKED],
+        "separate generic arguments by `, ` instead of `,` in MSVC-style debuginfo type names \
+        (default: no)"),
+    debuginfo_names_unit_void: bool = (false, parse_bool, [TRACKED],
+        "name the unit type `void` in MSVC-style debuginfo type names, e.g. `ptr_const$<void>`, \
+        like in the return type of function pointers (default: no)"),
+    debuginfo_names_unsized_hints: bool = (false, parse_bool, [TRACKED],
+        "mark structs that end in a slice, `str`, or trait object as unsized in debuginfo type \
+        names, e.g. `Foo{?Sized}` or `unsized$<Foo>` (default: no)"),
+    debuginfo_names_wrap_slice_refs: bool = (false, parse_bool, [TRACKED],
+        "wrap references to slices and `str` in `ref$<...>` in MSVC-style debuginfo type names, \
+        like all other references (default: no)"),
+    debuginfo_names_wrap_str_refs: bool = (false, parse_bool, [TRACKED],
+        "name references to `str` `str$<str>` or `str_mut$<str>` in MSVC-style debuginfo type \
+        names, which natvis can match on (default: no)"),
     deduplicate_diagnostics: bool = (true, parse_bool, [UNTRACKED],
         "deduplicate identical diagnostics (default: yes)"),
     dep_info_omit_d_target: bool = (false, parse_bool, [TRACKED],
@@ -1507,6 +1594,8 @@ mod parse {
         "validate MIR after each transformation"),
     verbose: bool = (false, parse_bool, [UNTRACKED],
         "in general, enable more debug printouts (default: no)"),
+    verify_debuginfo_type_names: bool = (false, parse_bool, [UNTRACKED],
+        "check that the computed debuginfo type names can be parsed back (default: no)"),
     verify_llvm_ir: bool = (false, parse_bool, [TRACKED],
         "verify LLVM IR (default: no)"),
     wasi_exec_model: Option<WasiExecModel> = (None, parse_wasi_exec_model, [TRACKED],
@@ -155,6 +155,7 @@
         C,
         CStr,
         CString,
+        Cell,
         Center,
         Clone,
         Continue,
@@ -169,6 +170,7 @@
         Default,
         Deref,
         DirBuilder,
+        Discriminant,
         Display,
         DoubleEndedIterator,
         Duration,
@@ -210,6 +212,18 @@
         LintPass,
         Mutex,
         N,
+        NonZeroI128,
+        NonZeroI16,
+        NonZeroI32,
+        NonZeroI64,
+        NonZeroI8,
+        NonZeroIsize,
+        NonZeroU128,
+        NonZeroU16,
+        NonZeroU32,
+        NonZeroU64,
+        NonZeroU8,
+        NonZeroUsize,
         None,
         Ok,
         Option,
@@ -239,6 +253,7 @@
         Rc,
         Ready,
         Receiver,
+        RefCell,
         Relaxed,
         Release,
         Result,
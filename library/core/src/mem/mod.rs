@@ -1026,6 +1026,7 @@ pub fn drop<T>(_x: T) {}
 ///
 /// See the [`discriminant`] function in this module for more information.
 #[stable(feature = "discriminant_value", since = "1.21.0")]
+#[cfg_attr(not(test), rustc_diagnostic_item = "Discriminant")]
 pub struct Discriminant<T>(<T as DiscriminantKind>::Discriminant);
 
 // N.B. These trait implementations cannot be derived because we don't want any bounds on T.
@@ -0,0 +1,32 @@
+// Checks that `-Z debuginfo-names-absent-auto-traits` marks trait objects that are not `Send`
+// or `Sync` in debuginfo type names, and that auto traits implied by a supertrait of the
+// principal trait count as present.
+
+// ignore-msvc
+// revisions: default absent
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[absent] compile-flags: -Zdebuginfo-names-absent-auto-traits
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "&dyn debuginfo_absent_auto_traits::Tr",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "&dyn debuginfo_absent_auto_traits::Tr + core::marker::Send",
+// absent-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "&dyn debuginfo_absent_auto_traits::Tr + !Send + !Sync",
+// absent-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "&dyn debuginfo_absent_auto_traits::Tr + core::marker::Send + !Sync",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "&dyn debuginfo_absent_auto_traits::ThreadSafe",
+
+#![crate_type = "lib"]
+
+pub trait Tr {}
+
+pub trait ThreadSafe: Send + Sync {}
+
+impl Tr for u8 {}
+
+impl ThreadSafe for u8 {}
+
+pub fn trait_objects(x: &u8) -> usize {
+    let plain: &dyn Tr = x;
+    let send: &(dyn Tr + Send) = x;
+    let thread_safe: &dyn ThreadSafe = x;
+    std::mem::size_of_val(plain) + std::mem::size_of_val(send) + std::mem::size_of_val(thread_safe)
+}
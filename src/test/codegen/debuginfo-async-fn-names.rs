@@ -0,0 +1,28 @@
+// Checks that `-Z debuginfo-names-async-fn-names` appends the name of the `async fn` to the
+// debuginfo type names of its state machine, so that the state machines of different
+// functions can be told apart by their unqualified names.
+
+// ignore-msvc
+// revisions: default names
+// compile-flags: -Cdebuginfo=2 -Copt-level=0 --edition=2018
+//[names] compile-flags: -Zdebuginfo-names-async-fn-names
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{async_fn_env#0}",
+// default-NOT: {fn=
+// names-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{async_fn_env#0}{fn=first}",
+// names-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{async_fn_env#0}{fn=second}",
+
+#![crate_type = "lib"]
+
+pub async fn first(x: u8) -> u8 {
+    x
+}
+
+pub async fn second(x: u16) -> u16 {
+    x
+}
+
+pub fn state_machines() {
+    let _first = first(1);
+    let _second = second(2);
+}
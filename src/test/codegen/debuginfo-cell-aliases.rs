@@ -0,0 +1,33 @@
+// Checks that `-Z debuginfo-names-aliases` names the interior mutability wrappers `Cell`,
+// `RefCell`, and `Mutex` by their aliases, and that they keep their full path by default.
+
+// revisions: default aliases
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[aliases] compile-flags: -Zdebuginfo-names-aliases
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<core::cell::Cell<u8>{{ ?}}>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<core::cell::RefCell<alloc::string::String>{{ ?}}>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<std::sync::mutex::Mutex<i32>{{ ?}}>",
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{Cell|cell\$}}<u8>{{ ?}}>",
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{RefCell|ref_cell\$}}<alloc::string::String>{{ ?}}>",
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{Mutex|mutex\$}}<i32>{{ ?}}>",
+
+#![crate_type = "lib"]
+
+use std::cell::{Cell, RefCell};
+use std::sync::Mutex;
+
+pub struct Wrapper<T>(T);
+
+pub fn cell(x: Wrapper<Cell<u8>>) -> Wrapper<Cell<u8>> {
+    x
+}
+
+pub fn ref_cell(x: Wrapper<RefCell<String>>) -> Wrapper<RefCell<String>> {
+    x
+}
+
+pub fn mutex(x: Wrapper<Mutex<i32>>) -> Wrapper<Mutex<i32>> {
+    x
+}
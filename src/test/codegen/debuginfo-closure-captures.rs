@@ -0,0 +1,31 @@
+// Checks that `-Z debuginfo-names-closure-captures` appends the captured types to the
+// debuginfo type names of closure environments, so that two closures of the same function
+// that capture different types can be told apart by name alone.
+
+// ignore-msvc
+// revisions: default captures
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[captures] compile-flags: -Zdebuginfo-names-closure-captures
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{closure_env#0}<u32>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{closure_env#1}<u32>",
+// captures-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{closure_env#0}<u32>{captures=(u8,)}",
+// captures-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{closure_env#1}<u32>{captures=(u32, &str)}",
+
+#![crate_type = "lib"]
+
+#[inline(never)]
+fn call<R>(f: impl FnOnce() -> R) -> R {
+    f()
+}
+
+pub fn capture_different_types<T: Copy>(t: T, a: u8, s: &str) -> (u8, T, &str) {
+    let first = call(move || a);
+    let (t, s) = call(move || (t, s));
+    (first, t, s)
+}
+
+pub fn instantiate() -> usize {
+    capture_different_types(1u32, 2, "three").2.len()
+}
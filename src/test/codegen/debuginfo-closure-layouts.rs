@@ -0,0 +1,30 @@
+// Checks that `-Z debuginfo-names-closure-layouts` names closures after the types of their
+// captures, in the order they are laid out in memory rather than the order they are
+// captured in, and that closures with the same captures are still told apart by their
+// disambiguator.
+
+// ignore-msvc
+// revisions: default layouts
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[layouts] compile-flags: -Zdebuginfo-names-closure-layouts
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{closure_env#0}",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{closure_env#1}",
+// layouts-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{closure_env#0}<u32, u8>",
+// layouts-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{closure_env#1}<u32, u8>",
+
+#![crate_type = "lib"]
+
+#[inline(never)]
+fn call<R>(f: impl FnOnce() -> R) -> R {
+    f()
+}
+
+pub fn capture_two(a: u8, b: u32) -> (u8, u32) {
+    call(move || (a, b))
+}
+
+pub fn capture_two_twice(a: u8, b: u32) -> ((u8, u32), (u8, u32)) {
+    (call(move || (a, b)), call(move || (a, b)))
+}
@@ -0,0 +1,44 @@
+// Checks that small array and tuple const generic arguments are spelled out in debuginfo
+// names, and that large ones are named by their hash.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithArray<{[1, 2, 3]}>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithTuple<{(1, 'x')}>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithNested<{([true], (7,))}>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithLargeArray<{CONST#{{[0-9a-f]+}}}>",
+
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithArray<const_array$<1,2,3> >",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithTuple<const_tuple$<1,120> >",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithNested<const_tuple$<const_array$<true>,const_tuple$<7> > >",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithLargeArray<CONST${{[0-9a-f]+}}>",
+
+// ignore-tidy-linelength
+
+#![crate_type = "lib"]
+#![feature(adt_const_params)]
+#![allow(incomplete_features)]
+
+pub struct WithArray<const X: [u8; 3]>;
+
+pub struct WithTuple<const X: (u8, char)>;
+
+pub struct WithNested<const X: ([bool; 1], (i8,))>;
+
+pub struct WithLargeArray<const X: [u8; 17]>;
+
+pub fn array(x: WithArray<{ [1, 2, 3] }>) -> WithArray<{ [1, 2, 3] }> {
+    x
+}
+
+pub fn tuple(x: WithTuple<{ (1, 'x') }>) -> WithTuple<{ (1, 'x') }> {
+    x
+}
+
+pub fn nested(x: WithNested<{ ([true], (7,)) }>) -> WithNested<{ ([true], (7,)) }> {
+    x
+}
+
+pub fn large_array(x: WithLargeArray<{ [0; 17] }>) -> WithLargeArray<{ [0; 17] }> {
+    x
+}
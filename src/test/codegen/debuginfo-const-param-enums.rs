@@ -0,0 +1,47 @@
+// Checks that const generic arguments of fieldless enum types are named by their variant in
+// debuginfo names, and that values of enums with fields are named by their hash.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithColor<debuginfo_const_param_enums::Color::Red>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithColor<debuginfo_const_param_enums::Color::Blue>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithShape<{CONST#{{[0-9a-f]+}}}>",
+
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithColor<debuginfo_const_param_enums::Color::Red>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithColor<debuginfo_const_param_enums::Color::Blue>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithShape<CONST${{[0-9a-f]+}}>",
+
+// ignore-tidy-linelength
+
+#![crate_type = "lib"]
+#![feature(adt_const_params)]
+#![allow(incomplete_features)]
+
+#[derive(PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(PartialEq, Eq)]
+pub enum Shape {
+    Dot,
+    Circle(u8),
+}
+
+pub struct WithColor<const C: Color>;
+
+pub struct WithShape<const S: Shape>;
+
+pub fn red(x: WithColor<{ Color::Red }>) -> WithColor<{ Color::Red }> {
+    x
+}
+
+pub fn blue(x: WithColor<{ Color::Blue }>) -> WithColor<{ Color::Blue }> {
+    x
+}
+
+pub fn circle(x: WithShape<{ Shape::Circle(3) }>) -> WithShape<{ Shape::Circle(3) }> {
+    x
+}
@@ -0,0 +1,65 @@
+// Checks that integer const generic arguments are rendered with their exact value at the
+// extremes of every fixed-size integer type, including `i128` and `u128`. Negative integers
+// are spelled `neg$` plus their magnitude in C++-like names. `isize` and `usize` are covered
+// by the fixed-size type of the same width.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+// ignore-tidy-linelength
+
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "I8<{{-|neg\$}}128>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "I8<127>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "I16<{{-|neg\$}}32768>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "I16<32767>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "I32<{{-|neg\$}}2147483648>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "I32<2147483647>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "I64<{{-|neg\$}}9223372036854775808>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "I64<9223372036854775807>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "I128<{{-|neg\$}}170141183460469231731687303715884105728>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "I128<170141183460469231731687303715884105727>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "U8<0>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "U8<255>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "U16<0>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "U16<65535>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "U32<0>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "U32<4294967295>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "U64<0>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "U64<18446744073709551615>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "U128<0>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "U128<340282366920938463463374607431768211455>",
+
+#![crate_type = "lib"]
+
+pub struct I8<const N: i8>(u8);
+pub struct I16<const N: i16>(u8);
+pub struct I32<const N: i32>(u8);
+pub struct I64<const N: i64>(u8);
+pub struct I128<const N: i128>(u8);
+pub struct U8<const N: u8>(u8);
+pub struct U16<const N: u16>(u8);
+pub struct U32<const N: u32>(u8);
+pub struct U64<const N: u64>(u8);
+pub struct U128<const N: u128>(u8);
+
+pub fn extremes(
+    i8_min: I8<{ i8::MIN }>,
+    i8_max: I8<{ i8::MAX }>,
+    i16_min: I16<{ i16::MIN }>,
+    i16_max: I16<{ i16::MAX }>,
+    i32_min: I32<{ i32::MIN }>,
+    i32_max: I32<{ i32::MAX }>,
+    i64_min: I64<{ i64::MIN }>,
+    i64_max: I64<{ i64::MAX }>,
+    i128_min: I128<{ i128::MIN }>,
+    i128_max: I128<{ i128::MAX }>,
+    u8_min: U8<{ u8::MIN }>,
+    u8_max: U8<{ u8::MAX }>,
+    u16_min: U16<{ u16::MIN }>,
+    u16_max: U16<{ u16::MAX }>,
+    u32_min: U32<{ u32::MIN }>,
+    u32_max: U32<{ u32::MAX }>,
+    u64_min: U64<{ u64::MIN }>,
+    u64_max: U64<{ u64::MAX }>,
+    u128_min: U128<{ u128::MIN }>,
+    u128_max: U128<{ u128::MAX }>,
+) {
+}
@@ -0,0 +1,44 @@
+// Checks that negative float const arguments are spelled `neg$` plus their magnitude in
+// C++-like debuginfo type names, so that the MSVC debugger doesn't take the `-` for an
+// operator, that non-finite values are spelled `inf$` and `nan$` there, and that all of them
+// keep their readable form in Rust-style names.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+// ignore-tidy-linelength
+
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithF64<-1.5>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithF64<-0.0>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithF64<-inf>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithF64<NaN>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithF64<2.5>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithF64<neg$1.5>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithF64<neg$0.0>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithF64<neg$inf$>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithF64<nan$>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithF64<2.5>",
+
+#![crate_type = "lib"]
+#![feature(adt_const_params)]
+#![allow(incomplete_features)]
+
+pub struct WithF64<const F: f64>;
+
+pub fn negative(x: WithF64<-1.5>) -> WithF64<-1.5> {
+    x
+}
+
+pub fn negative_zero(x: WithF64<{ -0.0 }>) -> WithF64<{ -0.0 }> {
+    x
+}
+
+pub fn negative_infinity(x: WithF64<{ f64::NEG_INFINITY }>) -> WithF64<{ f64::NEG_INFINITY }> {
+    x
+}
+
+pub fn nan(x: WithF64<{ f64::NAN }>) -> WithF64<{ f64::NAN }> {
+    x
+}
+
+pub fn positive(x: WithF64<2.5>) -> WithF64<2.5> {
+    x
+}
@@ -0,0 +1,32 @@
+// Checks that const generic arguments of different types get different hashes in their
+// debuginfo names, even if their values are represented by the same bytes.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+
+// NONMSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "WithA<{CONST#[[HASH:[0-9a-f]+]]}>",
+// NONMSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "WithB<{CONST#
+// NONMSVC-NOT: [[HASH]]
+// NONMSVC-SAME: }>",
+
+// MSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "WithA<CONST$[[HASH:[0-9a-f]+]]>",
+// MSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "WithB<CONST$
+// MSVC-NOT: [[HASH]]
+// MSVC-SAME: >",
+
+#![crate_type = "lib"]
+#![feature(adt_const_params)]
+#![allow(incomplete_features)]
+
+#[derive(PartialEq, Eq)]
+pub struct A(u8);
+
+#[derive(PartialEq, Eq)]
+pub struct B(u8);
+
+pub struct WithA<const X: A>;
+
+pub struct WithB<const X: B>;
+
+pub fn use_both(a: WithA<{ A(7) }>, b: WithB<{ B(7) }>) -> (WithA<{ A(7) }>, WithB<{ B(7) }>) {
+    (a, b)
+}
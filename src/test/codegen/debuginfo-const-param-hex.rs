@@ -0,0 +1,26 @@
+// Checks that `-Z debuginfo-names-hex-consts` names unsigned integer const arguments in
+// hexadecimal in debuginfo type names, and that signed ones and the default are unaffected.
+
+// revisions: default hex
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[hex] compile-flags: -Zdebuginfo-names-hex-consts
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Foo<65280>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Signed<{{-|neg\$}}5>",
+// hex-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Foo<0xff00>",
+// hex-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Signed<{{-|neg\$}}5>",
+
+#![crate_type = "lib"]
+
+pub struct Foo<const N: u16>;
+
+pub struct Signed<const N: i16>;
+
+pub fn unsigned_arg(x: Foo<{ 0xFF00u16 }>) -> Foo<{ 0xFF00u16 }> {
+    x
+}
+
+pub fn signed_arg(x: Signed<-5>) -> Signed<-5> {
+    x
+}
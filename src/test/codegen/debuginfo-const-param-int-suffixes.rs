@@ -0,0 +1,42 @@
+// Checks that `-Z debuginfo-names-int-suffixes` appends the type to integer const arguments
+// in debuginfo type names, and that they are bare numbers by default.
+
+// revisions: default suffixes
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[suffixes] compile-flags: -Zdebuginfo-names-int-suffixes
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithU8<5>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithI16<{{-|neg\$}}5>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithUsize<5>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithI128<{{-|neg\$}}170141183460469231731687303715884105728>",
+// suffixes-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithU8<5u8>",
+// suffixes-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithI16<{{-|neg\$}}5i16>",
+// suffixes-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithUsize<5usize>",
+// suffixes-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithI128<{{-|neg\$}}170141183460469231731687303715884105728i128>",
+
+#![crate_type = "lib"]
+
+pub struct WithU8<const N: u8>;
+
+pub struct WithI16<const N: i16>;
+
+pub struct WithUsize<const N: usize>;
+
+pub struct WithI128<const N: i128>;
+
+pub fn u8_arg(x: WithU8<5>) -> WithU8<5> {
+    x
+}
+
+pub fn i16_arg(x: WithI16<-5>) -> WithI16<-5> {
+    x
+}
+
+pub fn usize_arg(x: WithUsize<5>) -> WithUsize<5> {
+    x
+}
+
+pub fn i128_arg(x: WithI128<{ i128::MIN }>) -> WithI128<{ i128::MIN }> {
+    x
+}
@@ -0,0 +1,29 @@
+// Checks that negative integer const arguments are spelled `neg$` plus their magnitude in
+// C++-like debuginfo type names, so that the MSVC debugger doesn't take the `-` for an
+// operator, and that they keep their readable form in Rust-style names.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+// ignore-tidy-linelength
+
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithI32<-1>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithI32<-2147483648>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithI32<0>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithI32<neg$1>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithI32<neg$2147483648>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "WithI32<0>",
+
+#![crate_type = "lib"]
+
+pub struct WithI32<const N: i32>;
+
+pub fn minus_one(x: WithI32<-1>) -> WithI32<-1> {
+    x
+}
+
+pub fn min(x: WithI32<{ i32::MIN }>) -> WithI32<{ i32::MIN }> {
+    x
+}
+
+pub fn zero(x: WithI32<0>) -> WithI32<0> {
+    x
+}
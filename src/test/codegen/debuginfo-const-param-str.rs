@@ -0,0 +1,34 @@
+// Checks that `&str` const generic arguments are spelled out as escaped string literals in
+// debuginfo names, and that long strings are cut off.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Foo<{\22hi\22}>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Foo<{\22say \5C\22hi\5C\22\5Cnagain\22}>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Foo<{\22aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\22...}>",
+
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Foo<const_str$<hi> >",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Foo<const_str$<say$20$22hi$22$0aagain> >",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Foo<const_str$<aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa,truncated$> >",
+
+// ignore-tidy-linelength
+
+#![crate_type = "lib"]
+#![feature(adt_const_params)]
+#![allow(incomplete_features)]
+
+pub struct Foo<const S: &'static str>;
+
+pub fn short(x: Foo<"hi">) -> Foo<"hi"> {
+    x
+}
+
+pub fn escaped(x: Foo<"say \"hi\"\nagain">) -> Foo<"say \"hi\"\nagain"> {
+    x
+}
+
+pub fn long(
+    x: Foo<"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa">,
+) -> Foo<"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"> {
+    x
+}
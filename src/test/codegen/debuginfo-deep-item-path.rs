@@ -0,0 +1,36 @@
+// Checks that the debuginfo type names of items nested deep in modules contain their full
+// path, here 50 levels of macro-generated modules.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+// ignore-tidy-linelength
+
+// CHECK: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_deep_item_path::m00::m01::m02::m03::m04::m05::m06::m07::m08::m09::m10::m11::m12::m13::m14::m15::m16::m17::m18::m19::m20::m21::m22::m23::m24::m25::m26::m27::m28::m29::m30::m31::m32::m33::m34::m35::m36::m37::m38::m39::m40::m41::m42::m43::m44::m45::m46::m47::m48::m49::Deep>",
+
+#![crate_type = "lib"]
+
+macro_rules! nested_modules {
+    ($name:ident $($rest:ident)*) => {
+        pub mod $name {
+            nested_modules!($($rest)*);
+        }
+    };
+    () => {
+        pub struct Deep;
+    };
+}
+
+nested_modules! {
+    m00 m01 m02 m03 m04 m05 m06 m07 m08 m09
+    m10 m11 m12 m13 m14 m15 m16 m17 m18 m19
+    m20 m21 m22 m23 m24 m25 m26 m27 m28 m29
+    m30 m31 m32 m33 m34 m35 m36 m37 m38 m39
+    m40 m41 m42 m43 m44 m45 m46 m47 m48 m49
+}
+
+pub struct Wrapper<T>(T);
+
+pub type Deep = m00::m01::m02::m03::m04::m05::m06::m07::m08::m09::m10::m11::m12::m13::m14::m15::m16::m17::m18::m19::m20::m21::m22::m23::m24::m25::m26::m27::m28::m29::m30::m31::m32::m33::m34::m35::m36::m37::m38::m39::m40::m41::m42::m43::m44::m45::m46::m47::m48::m49::Deep;
+
+pub fn deep(x: Wrapper<Deep>) -> Wrapper<Deep> {
+    x
+}
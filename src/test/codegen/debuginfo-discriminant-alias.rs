@@ -0,0 +1,25 @@
+// Checks that `-Z debuginfo-names-aliases` names `Discriminant<T>` by its alias, so that
+// visualizers can tell which enum the stored tag belongs to, and that it keeps its full path
+// by default.
+
+// revisions: default aliases
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[aliases] compile-flags: -Zdebuginfo-names-aliases
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Discriminant<{{(enum\$<)?}}debuginfo_discriminant_alias::MyEnum{{(> )?}}>",
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{{Discriminant|discriminant\$}}<{{(enum\$<)?}}debuginfo_discriminant_alias::MyEnum{{(> )?}}>",
+
+#![crate_type = "lib"]
+
+use std::mem::{discriminant, Discriminant};
+
+pub enum MyEnum {
+    A(u32),
+    B { x: u8 },
+    C,
+}
+
+pub fn discriminant_of(e: &MyEnum) -> Discriminant<MyEnum> {
+    discriminant(e)
+}
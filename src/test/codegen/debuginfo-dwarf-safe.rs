@@ -0,0 +1,45 @@
+// Checks that `-Z debuginfo-names-dwarf-safe` leaves out the spaces in the separators of
+// debuginfo type names, and that names keep their spaces by default.
+
+// ignore-msvc
+// revisions: default safe
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[safe] compile-flags: -Zdebuginfo-names-dwarf-safe
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<(u8, u16), [u32; 2]>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Vec<(u8, u16), alloc::alloc::Global>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "&(dyn core::fmt::Debug + core::marker::Send)",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "simd<f32; 4>",
+// safe-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<(u8,u16),[u32;2]>",
+// safe-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Vec<(u8,u16),alloc::alloc::Global>",
+// safe-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "&(dyn core::fmt::Debug+core::marker::Send)",
+// safe-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "simd<f32;4>",
+
+#![crate_type = "lib"]
+#![feature(repr_simd)]
+#![allow(non_camel_case_types)]
+
+use std::fmt::Debug;
+
+pub struct Wrapper<T, U>(T, U);
+
+pub fn tuple(x: Wrapper<(u8, u16), [u32; 2]>) -> Wrapper<(u8, u16), [u32; 2]> {
+    x
+}
+
+pub fn vec(x: Vec<(u8, u16)>) -> usize {
+    x.len()
+}
+
+pub fn dyn_send(x: &(dyn Debug + Send)) -> &(dyn Debug + Send) {
+    x
+}
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct f32x4(f32, f32, f32, f32);
+
+pub fn simd(x: f32x4) -> f32x4 {
+    x
+}
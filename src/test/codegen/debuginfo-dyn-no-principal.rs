@@ -0,0 +1,18 @@
+// Checks the debuginfo type names of trait objects that only consist of auto traits. Their
+// order depends on the `DefPathHash` of the traits, so either is accepted here.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+// ignore-tidy-linelength
+
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "&dyn core::marker::Send",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "&dyn {{core::marker::Send \+ core::marker::Sync|core::marker::Sync \+ core::marker::Send}}",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "ref$<dyn$<core::marker::Send> >",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "ref$<dyn$<{{core::marker::Send,core::marker::Sync|core::marker::Sync,core::marker::Send}}> >",
+
+#![crate_type = "lib"]
+
+pub fn auto_traits_only(x: &u8) -> usize {
+    let send: &dyn Send = x;
+    let send_sync: &(dyn Send + Sync) = x;
+    std::mem::size_of_val(send) + std::mem::size_of_val(send_sync)
+}
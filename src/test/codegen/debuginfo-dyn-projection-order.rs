@@ -0,0 +1,32 @@
+// Checks that the associated item bindings of trait objects are ordered by the name of the
+// item in debuginfo type names with `-Z debuginfo-names-canonical`, independently of the
+// order they are declared or written in. By default they keep the order of the trait
+// object's predicates, which depends on the hashes of the items.
+
+// revisions: default canonical
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[canonical] compile-flags: -Zdebuginfo-names-canonical
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{{&dyn debuginfo_dyn_projection_order::Pair<(First=u8, Second=u16|Second=u16, First=u8)>|ref\$<dyn\$<debuginfo_dyn_projection_order::Pair<(assoc\$<First,u8>,assoc\$<Second,u16>|assoc\$<Second,u16>,assoc\$<First,u8>) > > >}}",
+// canonical-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{{&dyn debuginfo_dyn_projection_order::Pair<First=u8, Second=u16>|ref\$<dyn\$<debuginfo_dyn_projection_order::Pair<assoc\$<First,u8>,assoc\$<Second,u16> > > >}}",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "&dyn core::iter::traits::iterator::Iterator<Item=u8>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "ref$<dyn$<core::iter::traits::iterator::Iterator<assoc$<Item,u8> > > >",
+
+#![crate_type = "lib"]
+
+pub trait Pair {
+    type Second;
+    type First;
+}
+
+impl Pair for u8 {
+    type Second = u16;
+    type First = u8;
+}
+
+pub fn bindings(x: &u8, iter: &std::iter::Empty<u8>) -> usize {
+    let pair: &dyn Pair<Second = u16, First = u8> = x;
+    let iter: &dyn Iterator<Item = u8> = iter;
+    std::mem::size_of_val(pair) + std::mem::size_of_val(iter)
+}
@@ -0,0 +1,18 @@
+// Checks that the debuginfo type names of extern types are marked as such, so that debuggers
+// know that they have an unknown size and layout.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+
+// NONMSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "extern type Opaque",
+// MSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "extern_type$<Opaque>",
+
+#![crate_type = "lib"]
+#![feature(extern_types)]
+
+extern "C" {
+    pub type Opaque;
+}
+
+pub fn opaque(x: *const Opaque) -> *const Opaque {
+    x
+}
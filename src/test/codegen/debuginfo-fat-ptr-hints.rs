@@ -0,0 +1,36 @@
+// Checks that `-Z debuginfo-names-fat-ptr-hints` marks raw pointers to unsized types as fat
+// in C++-like debuginfo type names, including pointers to structs with an unsized tail, and
+// leaves thin pointers alone.
+
+// only-msvc
+// revisions: default hints
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[hints] compile-flags: -Zdebuginfo-names-fat-ptr-hints
+// ignore-tidy-linelength
+
+// CHECK-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "ptr_const$<u8>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "ptr_const$<dyn$<core::fmt::Debug> >",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "ptr_mut$<debuginfo_fat_ptr_hints::Tail<slice$<u8> > >",
+// hints-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "ptr_const$<dyn$<core::fmt::Debug>,fat>",
+// hints-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "ptr_mut$<debuginfo_fat_ptr_hints::Tail<slice$<u8> >,fat>",
+
+#![crate_type = "lib"]
+
+use std::fmt::Debug;
+
+pub struct Tail<T: ?Sized> {
+    pub len: usize,
+    pub data: T,
+}
+
+pub fn thin(x: *const u8) -> *const u8 {
+    x
+}
+
+pub fn fat(x: *const dyn Debug) -> *const dyn Debug {
+    x
+}
+
+pub fn fat_tail(x: *mut Tail<[u8]>) -> *mut Tail<[u8]> {
+    x
+}
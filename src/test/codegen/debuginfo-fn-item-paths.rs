@@ -0,0 +1,27 @@
+// Checks that `-Z debuginfo-names-fn-item-paths` tells apart function items with the same
+// signature by the path of the function, which takes the place of the signature in C++-like
+// names.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0 -Zdebuginfo-names-fn-item-paths
+// ignore-tidy-linelength
+
+// NONMSVC-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "fn(u8) -> u8 {debuginfo_fn_item_paths::double}",
+// NONMSVC-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "fn(u8) -> u8 {debuginfo_fn_item_paths::triple}",
+// MSVC-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "fn_item$<debuginfo_fn_item_paths::double>",
+// MSVC-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "fn_item$<debuginfo_fn_item_paths::triple>",
+
+#![crate_type = "lib"]
+
+pub fn double(x: u8) -> u8 {
+    x * 2
+}
+
+pub fn triple(x: u8) -> u8 {
+    x * 3
+}
+
+pub fn both(x: u8) -> u8 {
+    let double_fn = double;
+    let triple_fn = triple;
+    double_fn(x) + triple_fn(x)
+}
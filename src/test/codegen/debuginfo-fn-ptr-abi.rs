@@ -0,0 +1,41 @@
+// Checks that the calling convention of function pointers is part of their debuginfo name,
+// both for Rust-style names and for C++-like names, and that C variadic function pointers
+// get well-formed names.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+// ignore-tidy-linelength
+
+// NONMSVC: !DIDerivedType(tag: DW_TAG_pointer_type, name: "extern \22C\22 fn(u8)",
+// NONMSVC: !DIDerivedType(tag: DW_TAG_pointer_type, name: "extern \22system\22 fn(u8)",
+// NONMSVC: !DIDerivedType(tag: DW_TAG_pointer_type, name: "extern \22C-unwind\22 fn(u8) -> u8",
+// NONMSVC: !DIDerivedType(tag: DW_TAG_pointer_type, name: "extern \22C\22 fn(i32) -> i32",
+// NONMSVC: !DIDerivedType(tag: DW_TAG_pointer_type, name: "unsafe extern \22C\22 fn(i32, ...) -> i32",
+
+// MSVC: !DIDerivedType(tag: DW_TAG_pointer_type, name: "extern_C$ void (*)(u8)",
+// MSVC: !DIDerivedType(tag: DW_TAG_pointer_type, name: "extern_system$ void (*)(u8)",
+// MSVC: !DIDerivedType(tag: DW_TAG_pointer_type, name: "extern_C_unwind$ u8 (*)(u8)",
+// MSVC: !DIDerivedType(tag: DW_TAG_pointer_type, name: "extern_C$ i32 (*)(i32)",
+// MSVC: !DIDerivedType(tag: DW_TAG_pointer_type, name: "extern_C$ i32 (*)(i32,...)",
+
+#![crate_type = "lib"]
+#![feature(c_unwind)]
+
+pub fn c(f: extern "C" fn(u8)) -> extern "C" fn(u8) {
+    f
+}
+
+pub fn system(f: extern "system" fn(u8)) -> extern "system" fn(u8) {
+    f
+}
+
+pub fn c_unwind(f: extern "C-unwind" fn(u8) -> u8) -> extern "C-unwind" fn(u8) -> u8 {
+    f
+}
+
+pub fn non_variadic(f: extern "C" fn(i32) -> i32) -> extern "C" fn(i32) -> i32 {
+    f
+}
+
+pub fn variadic(f: unsafe extern "C" fn(i32, ...) -> i32) -> unsafe extern "C" fn(i32, ...) -> i32 {
+    f
+}
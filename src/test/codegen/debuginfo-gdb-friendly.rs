@@ -0,0 +1,30 @@
+// Checks that `-Z debuginfo-names-gdb-friendly` names arrays and slices `array<T, N>` and
+// `slice<T>` instead of using square brackets, and leaves other types alone.
+
+// ignore-msvc
+// revisions: default gdb
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[gdb] compile-flags: -Zdebuginfo-names-gdb-friendly
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<[u8; 4]>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "&[u8]",
+// gdb-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<array<u8, 4>>",
+// gdb-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "&slice<u8>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<(u8, u16)>",
+
+#![crate_type = "lib"]
+
+pub struct Wrapper<T>(T);
+
+pub fn array(x: Wrapper<[u8; 4]>) -> Wrapper<[u8; 4]> {
+    x
+}
+
+pub fn slice(x: &[u8]) -> usize {
+    x.len()
+}
+
+pub fn tuple(x: Wrapper<(u8, u16)>) -> Wrapper<(u8, u16)> {
+    x
+}
@@ -0,0 +1,53 @@
+// Checks that the debuginfo names of closure environments, generators, and the different
+// kinds of async state machines each carry their own kind label, so that debuggers and
+// natvis visualizers can tell them apart.
+//
+// The order that debuginfo gets emitted into LLVM IR is hard to predict once async fns
+// are involved, so DAG allows any order.
+
+// compile-flags: -Cdebuginfo=2 --edition 2018 -Copt-level=0
+
+// NONMSVC-DAG: name: "{closure_env#0}", scope: ![[closure_NAMESPACE:[0-9]+]],
+// NONMSVC-DAG: ![[closure_NAMESPACE]] = !DINamespace(name: "closure"
+// NONMSVC-DAG: name: "{generator_env#0}"
+// NONMSVC-DAG: name: "{async_fn_env#0}"
+// NONMSVC-DAG: name: "{async_block_env#0}"
+// NONMSVC-DAG: name: "{async_closure_env#0}"
+
+// MSVC-DAG: name: "closure_env$0", scope: ![[closure_NAMESPACE:[0-9]+]],
+// MSVC-DAG: ![[closure_NAMESPACE]] = !DINamespace(name: "closure"
+// MSVC-DAG: name: "generator_env$0"
+// MSVC-DAG: name: "async_fn_env$0"
+// MSVC-DAG: name: "async_block_env$0"
+// MSVC-DAG: name: "async_closure_env$0"
+
+#![crate_type = "lib"]
+#![feature(async_closure, generators)]
+
+pub fn closure() -> u32 {
+    let closure = || 1;
+    closure()
+}
+
+pub fn generator() {
+    let generator = || {
+        yield 1;
+    };
+    drop(generator);
+}
+
+pub async fn async_fn() {}
+
+pub fn async_fn_future() {
+    drop(async_fn());
+}
+
+pub fn async_block() {
+    let future = async {};
+    drop(future);
+}
+
+pub fn async_closure() {
+    let closure = async || {};
+    drop(closure());
+}
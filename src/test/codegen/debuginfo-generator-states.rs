@@ -0,0 +1,32 @@
+// Checks that `-Z debuginfo-names-generator-states` appends the number of states to the
+// debuginfo type names of generators: one per `yield`, plus the unresumed, returned, and
+// poisoned states.
+
+// ignore-msvc
+// revisions: default states
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[states] compile-flags: -Zdebuginfo-names-generator-states
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{generator_env#0}",
+// default-NOT: {states=
+// states-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{generator_env#0}{states=4}",
+// states-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{generator_env#0}{states=6}",
+
+#![crate_type = "lib"]
+#![feature(generators, generator_trait)]
+
+use std::ops::Generator;
+
+pub fn one_yield() -> impl Generator<Yield = u8, Return = ()> {
+    || {
+        yield 1;
+    }
+}
+
+pub fn three_yields() -> impl Generator<Yield = u8, Return = ()> {
+    || {
+        yield 1;
+        yield 2;
+        yield 3;
+    }
+}
@@ -0,0 +1,26 @@
+// Checks that `-Z debuginfo-names-layout-hints` appends the size and alignment of ADTs to
+// their debuginfo type names.
+
+// only-x86_64
+// compile-flags: -Cdebuginfo=2 -Copt-level=0 -Zdebuginfo-names-layout-hints
+// ignore-tidy-linelength
+
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Padded<u8> /*size=8 align=4*/",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Padded<u64> /*size=16 align=8*/",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Padded<u8>$size8$align4",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Padded<u64>$size16$align8",
+
+#![crate_type = "lib"]
+
+pub struct Padded<T> {
+    _a: u32,
+    _b: T,
+}
+
+pub fn small(x: Padded<u8>) -> Padded<u8> {
+    x
+}
+
+pub fn large(x: Padded<u64>) -> Padded<u64> {
+    x
+}
@@ -0,0 +1,29 @@
+// Checks that mutable references to slices and `str` keep their mutability in C++-like
+// debuginfo type names, even though references to them are not wrapped in `ref$<...>`,
+// and that immutable ones are still named like the slice or `str` itself.
+
+// only-msvc
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "str",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "mut_str$<str>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "slice$<u8>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "mut_slice$<slice$<u8> >",
+
+#![crate_type = "lib"]
+
+pub fn str_ref(x: &str) -> usize {
+    x.len()
+}
+
+pub fn str_mut(x: &mut str) -> usize {
+    x.len()
+}
+
+pub fn slice_ref(x: &[u8]) -> usize {
+    x.len()
+}
+
+pub fn slice_mut(x: &mut [u8]) -> usize {
+    x.len()
+}
@@ -0,0 +1,30 @@
+// Checks that `-Z debuginfo-names-max-len` shortens long type names at a component
+// boundary, keeps their brackets balanced, and that distinct types end up with distinct
+// hash suffixes.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0 -Zdebuginfo-names-max-len=80
+// ignore-tidy-linelength
+
+// NONMSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_names_max_len::Wrapper<{truncated#[[HASH_A:[0-9a-f]{16}]]}>>",
+// NONMSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_names_max_len::Wrapper<{truncated#
+// NONMSVC-NOT: [[HASH_A]]
+// NONMSVC-SAME: }>>",
+
+// MSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_names_max_len::Wrapper<TRUNCATED$[[HASH_A:[0-9a-f]{16}]]> >",
+// MSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_names_max_len::Wrapper<TRUNCATED$
+// MSVC-NOT: [[HASH_A]]
+// MSVC-SAME: > >",
+
+#![crate_type = "lib"]
+
+pub struct Wrapper<T>(T);
+
+pub type Deep<T> = Wrapper<Wrapper<Wrapper<Wrapper<Wrapper<Wrapper<T>>>>>>;
+
+pub fn deep_u8(x: Deep<u8>) -> Deep<u8> {
+    x
+}
+
+pub fn deep_u16(x: Deep<u16>) -> Deep<u16> {
+    x
+}
@@ -0,0 +1,38 @@
+// Checks that C++-like debuginfo type names never contain `>>`, which the MSVC debugger
+// would parse as a shift operator, even for deeply nested generics, enums that use the
+// enum fallback encoding, and trait objects with associated type bindings.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+// ignore-tidy-linelength
+
+// NONMSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "A<debuginfo_nested_generics_msvc::B<debuginfo_nested_generics_msvc::C<u8>>>",
+
+// MSVC-NOT: name: "{{[^"]*>>}}
+// MSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "A<debuginfo_nested_generics_msvc::B<debuginfo_nested_generics_msvc::C<u8> > >",
+// MSVC-NOT: name: "{{[^"]*>>}}
+
+#![crate_type = "lib"]
+
+pub struct A<T>(T);
+pub struct B<T>(T);
+pub struct C<T>(T);
+
+pub trait Trait<T> {
+    type Assoc;
+}
+
+impl<T> Trait<T> for u8 {
+    type Assoc = C<C<u8>>;
+}
+
+pub fn nested(x: A<B<C<u8>>>) -> A<B<C<u8>>> {
+    x
+}
+
+pub fn enum_fallback(x: Option<A<B<C<u8>>>>) -> Option<A<B<C<u8>>>> {
+    x
+}
+
+pub fn trait_object(x: &dyn Trait<A<B<C<u8>>>, Assoc = C<C<u8>>>) -> usize {
+    x as *const _ as *const u8 as usize
+}
@@ -0,0 +1,42 @@
+// Checks that `-Z debuginfo-names-niche-hints` appends the niche range and the dataful
+// variant to the Rust-style names of niche-optimized enums, and that other enums don't get
+// a hint.
+
+// ignore-msvc
+// revisions: default hints
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[hints] compile-flags: -Zdebuginfo-names-niche-hints
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<core::option::Option<&u8>>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_niche_hints::Niche>",
+// hints-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<core::option::Option<&u8>{niche#1..={{[0-9]+}}#Some}>",
+// hints-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_niche_hints::Niche{niche#0..=1#Data}>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_niche_hints::Tagged>",
+
+#![crate_type = "lib"]
+
+pub struct Wrapper<T>(T);
+
+pub enum Niche {
+    Data(bool),
+    Empty,
+    Other,
+}
+
+pub enum Tagged {
+    A(u8),
+    B(u8),
+}
+
+pub fn option(x: Wrapper<Option<&u8>>) -> Wrapper<Option<&u8>> {
+    x
+}
+
+pub fn niche(x: Wrapper<Niche>) -> Wrapper<Niche> {
+    x
+}
+
+pub fn tagged(x: Wrapper<Tagged>) -> Wrapper<Tagged> {
+    x
+}
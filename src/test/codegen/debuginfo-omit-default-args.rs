@@ -0,0 +1,49 @@
+// Checks that `-Z debuginfo-names-omit-default-args` leaves out trailing generic arguments
+// that are the same as the defaults of their parameters, like the allocator of `Box`, the
+// hasher of `HashMap`, or the parameters of user-defined types and traits, and that they are
+// kept by default.
+
+// revisions: default omit
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[omit] compile-flags: -Zdebuginfo-names-omit-default-args
+// ignore-tidy-linelength
+
+// The separators differ between C++-like and Rust-style names, so they are matched by
+// regular expressions here.
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<alloc::boxed::Box<u8,{{ ?}}alloc::alloc::Global>{{ ?}}>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<std::collections::hash::map::HashMap<u32,{{ ?}}u8,{{ ?}}std::collections::hash::map::RandomState>{{ ?}}>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_omit_default_args::Foo<u8,{{ ?}}{{\(\)|tuple\$<>}}>{{ ?}}>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{{&dyn |ref\$<dyn\$<}}debuginfo_omit_default_args::Tr<u8>
+// omit-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<alloc::boxed::Box<u8>{{ ?}}>",
+// omit-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<std::collections::hash::map::HashMap<u32,{{ ?}}u8>{{ ?}}>",
+// omit-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_omit_default_args::Foo<u8>{{ ?}}>",
+// omit-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{{&dyn |ref\$<dyn\$<}}debuginfo_omit_default_args::Tr{{[">]}}
+
+#![crate_type = "lib"]
+
+use std::collections::HashMap;
+
+pub struct Wrapper<T>(T);
+
+pub fn boxed(x: Wrapper<Box<u8>>) -> Wrapper<Box<u8>> {
+    x
+}
+
+pub fn hash_map(x: Wrapper<HashMap<u32, u8>>) -> Wrapper<HashMap<u32, u8>> {
+    x
+}
+
+pub struct Foo<T, U = ()>(T, U);
+
+pub fn foo(x: Wrapper<Foo<u8>>) -> Wrapper<Foo<u8>> {
+    x
+}
+
+pub trait Tr<Rhs = u8> {}
+
+impl Tr for u32 {}
+
+pub fn trait_object(x: &u32) -> &dyn Tr {
+    x
+}
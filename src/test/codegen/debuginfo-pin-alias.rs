@@ -0,0 +1,29 @@
+// Checks that `-Z debuginfo-names-aliases` names `Pin<P>` by its alias while still naming
+// the pointer type `P` in full, using a pinned generator like in async code.
+
+// revisions: default aliases
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[aliases] compile-flags: -Zdebuginfo-names-aliases
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<core::pin::Pin<{{&mut |ref_mut\$<}}debuginfo_pin_alias::start::{{(.generator_env#0.|generator_env\$0)}}
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{Pin|pin\$}}<{{&mut |ref_mut\$<}}debuginfo_pin_alias::start::{{(.generator_env#0.|generator_env\$0)}}
+
+#![crate_type = "lib"]
+#![feature(generators, generator_trait)]
+
+use std::ops::Generator;
+use std::pin::Pin;
+
+pub struct Wrapper<T>(T);
+
+fn resume<G: Generator<Yield = u8, Return = ()>>(g: Wrapper<Pin<&mut G>>) {
+    let _ = g.0.resume(());
+}
+
+pub fn start() {
+    let mut generator = || {
+        yield 1u8;
+    };
+    resume(Wrapper(Pin::new(&mut generator)));
+}
@@ -0,0 +1,26 @@
+// Checks that `-Z debuginfo-names-aliases` names ranges by their aliases, which natvis can
+// show like `start..end`, and that they keep their full path by default.
+
+// revisions: default aliases
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[aliases] compile-flags: -Zdebuginfo-names-aliases
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<core::ops::range::Range<usize>{{ ?}}>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<core::ops::range::RangeInclusive<i32>{{ ?}}>",
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{Range|range\$}}<usize>{{ ?}}>",
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{RangeInclusive|range_inclusive\$}}<i32>{{ ?}}>",
+
+#![crate_type = "lib"]
+
+use std::ops::{Range, RangeInclusive};
+
+pub struct Wrapper<T>(T);
+
+pub fn range(x: Wrapper<Range<usize>>) -> Wrapper<Range<usize>> {
+    x
+}
+
+pub fn range_inclusive(x: Wrapper<RangeInclusive<i32>>) -> Wrapper<RangeInclusive<i32>> {
+    x
+}
@@ -0,0 +1,27 @@
+// Checks that `-Z debuginfo-names-aliases` names the reference-counted pointers `Arc` and
+// `Rc` by their aliases, and that they keep their full path by default.
+
+// revisions: default aliases
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[aliases] compile-flags: -Zdebuginfo-names-aliases
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<alloc::sync::Arc<u8>{{ ?}}>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<alloc::rc::Rc<alloc::string::String>{{ ?}}>",
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{Arc|arc\$}}<u8>{{ ?}}>",
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{Rc|rc\$}}<alloc::string::String>{{ ?}}>",
+
+#![crate_type = "lib"]
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+pub struct Wrapper<T>(T);
+
+pub fn arc(x: Wrapper<Arc<u8>>) -> Wrapper<Arc<u8>> {
+    x
+}
+
+pub fn rc(x: Wrapper<Rc<String>>) -> Wrapper<Rc<String>> {
+    x
+}
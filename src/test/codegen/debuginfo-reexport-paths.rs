@@ -0,0 +1,34 @@
+// Checks that `-Z debuginfo-names-reexport-paths` names standard library types by the path
+// they are re-exported under, e.g. `std::collections::HashMap` instead of
+// `std::collections::hash::map::HashMap`, and that local types keep the path they are
+// defined at.
+
+// revisions: default reexport
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[reexport] compile-flags: -Zdebuginfo-names-reexport-paths
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<std::collections::hash::map::HashMap<u32,{{ ?}}alloc::string::String,{{ ?}}std::collections::hash::map::RandomState>{{ ?}}>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_reexport_paths::private::Local>",
+// reexport-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<std::collections::HashMap<u32,{{ ?}}std::string::String,{{ ?}}std::collections::hash_map::RandomState>{{ ?}}>",
+// reexport-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_reexport_paths::private::Local>",
+
+#![crate_type = "lib"]
+
+use std::collections::HashMap;
+
+pub use private::Local;
+
+pub struct Wrapper<T>(T);
+
+mod private {
+    pub struct Local;
+}
+
+pub fn hash_map(x: Wrapper<HashMap<u32, String>>) -> Wrapper<HashMap<u32, String>> {
+    x
+}
+
+pub fn local(x: Wrapper<Local>) -> Wrapper<Local> {
+    x
+}
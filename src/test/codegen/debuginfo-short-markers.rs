@@ -0,0 +1,23 @@
+// Checks that `-Z debuginfo-names-short-markers` drops the path of `PhantomData` in
+// debuginfo type names, while everything else stays fully qualified.
+
+// revisions: default short
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[short] compile-flags: -Zdebuginfo-names-short-markers
+// ignore-tidy-linelength
+
+// The separators differ between C++-like and Rust-style names, so they are matched by
+// regular expressions here.
+
+// default: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<core::marker::PhantomData<alloc::vec::Vec<u8,{{ ?}}alloc::alloc::Global>{{ ?}}>{{ ?}}>",
+// short: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<PhantomData<alloc::vec::Vec<u8,{{ ?}}alloc::alloc::Global>{{ ?}}>{{ ?}}>",
+
+#![crate_type = "lib"]
+
+use std::marker::PhantomData;
+
+pub struct Wrapper<T>(T);
+
+pub fn phantom(x: Wrapper<PhantomData<Vec<u8>>>) -> Wrapper<PhantomData<Vec<u8>>> {
+    x
+}
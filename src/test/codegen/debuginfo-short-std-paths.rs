@@ -0,0 +1,46 @@
+// Checks that `-Z debuginfo-names-short-std-paths` drops the path of common standard library
+// types in debuginfo type names, while other types, including types of the same name from
+// other crates, stay fully qualified.
+
+// revisions: default short
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[short] compile-flags: -Zdebuginfo-names-short-std-paths
+// ignore-tidy-linelength
+
+// The separators differ between C++-like and Rust-style names, so they are matched by
+// regular expressions here.
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<alloc::vec::Vec<u8,{{ ?}}alloc::alloc::Global>{{ ?}}>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<std::collections::hash::map::HashMap<u32,{{ ?}}alloc::string::String,{{ ?}}std::collections::hash::map::RandomState>{{ ?}}>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<alloc::collections::btree::map::BTreeMap<u32,{{ ?}}u8>{{ ?}}>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_short_std_paths::my::Vec>",
+// short-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<Vec<u8,{{ ?}}alloc::alloc::Global>{{ ?}}>",
+// short-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<HashMap<u32,{{ ?}}String,{{ ?}}std::collections::hash::map::RandomState>{{ ?}}>",
+// short-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<BTreeMap<u32,{{ ?}}u8>{{ ?}}>",
+// short-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_short_std_paths::my::Vec>",
+
+#![crate_type = "lib"]
+
+use std::collections::{BTreeMap, HashMap};
+
+pub struct Wrapper<T>(T);
+
+pub mod my {
+    pub struct Vec;
+}
+
+pub fn vec(x: Wrapper<Vec<u8>>) -> Wrapper<Vec<u8>> {
+    x
+}
+
+pub fn hash_map(x: Wrapper<HashMap<u32, String>>) -> Wrapper<HashMap<u32, String>> {
+    x
+}
+
+pub fn btree_map(x: Wrapper<BTreeMap<u32, u8>>) -> Wrapper<BTreeMap<u32, u8>> {
+    x
+}
+
+pub fn my_vec(x: Wrapper<my::Vec>) -> Wrapper<my::Vec> {
+    x
+}
@@ -0,0 +1,27 @@
+// Checks that SIMD vector types get a canonical debuginfo name made of their element type
+// and lane count.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+
+// NONMSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "simd<f32; 4>",
+// NONMSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "simd<i16; 8>",
+// MSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "vector$<f32,4>",
+// MSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "vector$<i16,8>",
+
+#![crate_type = "lib"]
+#![feature(repr_simd)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct f32x4(f32, f32, f32, f32);
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct i16x8(i16, i16, i16, i16, i16, i16, i16, i16);
+
+pub fn use_vectors(a: f32x4, b: i16x8) -> (f32x4, i16x8) {
+    let local_a = a;
+    let local_b = b;
+    (local_a, local_b)
+}
@@ -0,0 +1,20 @@
+// Checks that `-Z debuginfo-names-spaced-separators` puts a space after every comma in
+// C++-like debuginfo type names, including the one between the element type and the length
+// of an array.
+
+// only-msvc
+// revisions: default spaced
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[spaced] compile-flags: -Zdebuginfo-names-spaced-separators
+// ignore-tidy-linelength
+
+// default: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<array$<u32,2>,tuple$<u8,u16> >",
+// spaced: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<array$<u32, 2>, tuple$<u8, u16> >",
+
+#![crate_type = "lib"]
+
+pub struct Wrapper<T, U>(T, U);
+
+pub fn array(x: Wrapper<[u32; 2], (u8, u16)>) -> Wrapper<[u32; 2], (u8, u16)> {
+    x
+}
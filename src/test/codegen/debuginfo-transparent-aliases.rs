@@ -0,0 +1,27 @@
+// Checks that `-Z debuginfo-names-aliases` names the transparent wrappers `MaybeUninit` and
+// `ManuallyDrop` by their aliases, so that debuggers can see through them to the wrapped
+// value, and that they keep their full path by default.
+
+// revisions: default aliases
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[aliases] compile-flags: -Zdebuginfo-names-aliases
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{(union\$<)?}}core::mem::maybe_uninit::MaybeUninit<u32>{{( >)?}}{{ ?}}>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<core::mem::manually_drop::ManuallyDrop<alloc::string::String>{{ ?}}>",
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{MaybeUninit|maybe_uninit\$}}<u32>{{ ?}}>",
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{ManuallyDrop|manually_drop\$}}<alloc::string::String>{{ ?}}>",
+
+#![crate_type = "lib"]
+
+use std::mem::{ManuallyDrop, MaybeUninit};
+
+pub struct Wrapper<T>(T);
+
+pub fn maybe_uninit(x: Wrapper<MaybeUninit<u32>>) -> Wrapper<MaybeUninit<u32>> {
+    x
+}
+
+pub fn manually_drop(x: Wrapper<ManuallyDrop<String>>) -> Wrapper<ManuallyDrop<String>> {
+    x
+}
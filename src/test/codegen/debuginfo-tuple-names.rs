@@ -0,0 +1,33 @@
+// Checks the debuginfo type names of tuples of zero, one, and two elements. Like in Rust, a
+// tuple of one has a trailing comma in Rust-style names, which tells it apart from a
+// parenthesized type. C++-like names don't need one.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+// ignore-tidy-linelength
+
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<()>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<(u8,)>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<(u8, u16)>",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "(u8,)",
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "(u8, u16)",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<tuple$<> >",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<tuple$<u8> >",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<tuple$<u8,u16> >",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "tuple$<u8>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "tuple$<u8,u16>",
+
+#![crate_type = "lib"]
+
+pub struct Wrapper<T>(T);
+
+pub fn empty(x: Wrapper<()>) -> Wrapper<()> {
+    x
+}
+
+pub fn one(x: Wrapper<(u8,)>) -> Wrapper<(u8,)> {
+    x
+}
+
+pub fn two(x: Wrapper<(u8, u16)>) -> Wrapper<(u8, u16)> {
+    x
+}
@@ -0,0 +1,34 @@
+// Checks that `-Z debuginfo-names-aliases` names the `NonZero*` integer types after the
+// integer type they wrap, and that they keep their full path by default. Types that merely
+// share the name of a library type are not affected.
+
+// revisions: default aliases
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[aliases] compile-flags: -Zdebuginfo-names-aliases
+// ignore-tidy-linelength
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<core::num::nonzero::NonZeroU8>",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<core::num::nonzero::NonZeroUsize>",
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{NonZero|nonzero\$}}<u8>{{ ?}}>",
+// aliases-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<{{NonZero|nonzero\$}}<usize>{{ ?}}>",
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Wrapper<debuginfo_type_aliases::NonZeroU32>",
+
+#![crate_type = "lib"]
+
+use std::num::{NonZeroU8, NonZeroUsize};
+
+pub struct Wrapper<T>(T);
+
+pub struct NonZeroU32(u32);
+
+pub fn nonzero_u8(x: Wrapper<NonZeroU8>) -> Wrapper<NonZeroU8> {
+    x
+}
+
+pub fn nonzero_usize(x: Wrapper<NonZeroUsize>) -> Wrapper<NonZeroUsize> {
+    x
+}
+
+pub fn not_an_alias(x: Wrapper<NonZeroU32>) -> Wrapper<NonZeroU32> {
+    x
+}
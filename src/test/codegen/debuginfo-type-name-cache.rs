@@ -0,0 +1,33 @@
+// Checks that a type which is used in many places gets the same debuginfo type name
+// everywhere and that its type description is only emitted once.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+// ignore-tidy-linelength
+
+// NONMSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "Vec<std::collections::hash::map::HashMap<u32, alloc::string::String, std::collections::hash::map::RandomState>, alloc::alloc::Global>",
+// NONMSVC-NOT: !DICompositeType(tag: DW_TAG_structure_type, name: "Vec<std::collections::hash::map::HashMap<u32, alloc::string::String, std::collections::hash::map::RandomState>, alloc::alloc::Global>",
+// MSVC: !DICompositeType(tag: DW_TAG_structure_type, name: "Vec<std::collections::hash::map::HashMap<u32,alloc::string::String,std::collections::hash::map::RandomState>,alloc::alloc::Global>",
+// MSVC-NOT: !DICompositeType(tag: DW_TAG_structure_type, name: "Vec<std::collections::hash::map::HashMap<u32,alloc::string::String,std::collections::hash::map::RandomState>,alloc::alloc::Global>",
+
+#![crate_type = "lib"]
+
+use std::collections::HashMap;
+
+macro_rules! many_fns {
+    ($($name:ident)*) => {
+        $(
+            pub fn $name(x: &mut Vec<HashMap<u32, String>>) -> usize {
+                let local: Vec<HashMap<u32, String>> = Vec::new();
+                x.len() + local.len()
+            }
+        )*
+    }
+}
+
+many_fns! {
+    f00 f01 f02 f03 f04 f05 f06 f07 f08 f09
+    f10 f11 f12 f13 f14 f15 f16 f17 f18 f19
+    f20 f21 f22 f23 f24 f25 f26 f27 f28 f29
+    f30 f31 f32 f33 f34 f35 f36 f37 f38 f39
+    f40 f41 f42 f43 f44 f45 f46 f47 f48 f49
+}
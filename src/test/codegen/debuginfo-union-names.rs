@@ -0,0 +1,31 @@
+// Checks that unions are wrapped in `union$<...>` in C++-like debuginfo type names, while
+// their Rust-style names look just like the ones of structs.
+
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+// ignore-tidy-linelength
+
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_union_type, name: "Union<u8, u16>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_union_type, name: "union$<debuginfo_union_names::Union<u8,u16> >",
+
+// NONMSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Struct<u8, u16>",
+// MSVC-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Struct<u8,u16>",
+
+#![crate_type = "lib"]
+
+pub union Union<A: Copy, B: Copy> {
+    pub a: A,
+    pub b: B,
+}
+
+pub struct Struct<A, B> {
+    pub a: A,
+    pub b: B,
+}
+
+pub fn union_param(x: Union<u8, u16>) -> u16 {
+    unsafe { x.b }
+}
+
+pub fn struct_param(x: Struct<u8, u16>) -> u16 {
+    x.b
+}
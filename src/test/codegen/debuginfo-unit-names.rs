@@ -0,0 +1,41 @@
+// Checks the debuginfo type names of the unit type on its own and in the return type of
+// function pointers, where Rust-style names omit it and C++-like names write `void`, and
+// that `-Z debuginfo-names-unit-void` names it `void` everywhere in C++-like names, except
+// for the parameters of function pointers.
+
+// revisions: default void
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[void] compile-flags: -Zdebuginfo-names-unit-void
+// ignore-tidy-linelength
+
+// NONMSVC-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "fn()",
+// NONMSVC-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "fn(())",
+// NONMSVC-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "fn() -> u8",
+
+// default-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "{{\*const \(\)|ptr_const\$<tuple\$<> >}}",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{{Wrapper<\(\)>|Wrapper<tuple\$<> >}}",
+// void-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "{{\*const \(\)|ptr_const\$<void>}}",
+// void-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "{{Wrapper<\(\)>|Wrapper<void>}}",
+// MSVC-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "void (*)()",
+// MSVC-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "void (*)(tuple$<>)",
+// MSVC-DAG: !DIDerivedType(tag: DW_TAG_pointer_type, name: "u8 (*)()",
+
+#![crate_type = "lib"]
+
+pub struct Wrapper<T>(T);
+
+pub fn standalone(x: *const (), y: Wrapper<()>) -> (*const (), Wrapper<()>) {
+    (x, y)
+}
+
+pub fn returns_unit(f: fn()) -> fn() {
+    f
+}
+
+pub fn takes_unit(f: fn(())) -> fn(()) {
+    f
+}
+
+pub fn returns_u8(f: fn() -> u8) -> fn() -> u8 {
+    f
+}
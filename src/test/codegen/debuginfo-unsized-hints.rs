@@ -0,0 +1,34 @@
+// Checks that `-Z debuginfo-names-unsized-hints` marks structs with an unsized tail in their
+// debuginfo type names, and leaves sized instances of generic structs alone.
+
+// ignore-msvc
+// revisions: default hints
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[hints] compile-flags: -Zdebuginfo-names-unsized-hints
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Packet",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Tail<[u8]>",
+// default-NOT: {?Sized}
+// hints-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Packet{?Sized}",
+// hints-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Tail<[u8]>{?Sized}",
+// hints-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "Tail<[u8; 4]>",
+
+#![crate_type = "lib"]
+
+pub struct Packet {
+    pub len: u32,
+    pub data: [u8],
+}
+
+pub struct Tail<T: ?Sized> {
+    pub len: u32,
+    pub data: T,
+}
+
+pub fn packet(x: &Packet) -> &Packet {
+    x
+}
+
+pub fn tail(x: &Tail<[u8]>, y: Tail<[u8; 4]>) -> (&Tail<[u8]>, Tail<[u8; 4]>) {
+    (x, y)
+}
@@ -0,0 +1,22 @@
+// Checks that references to slices and `str` are only wrapped in `ref$<...>` in C++-like
+// debuginfo type names with `-Z debuginfo-names-wrap-slice-refs`.
+
+// only-msvc
+// revisions: default wrapped
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[wrapped] compile-flags: -Zdebuginfo-names-wrap-slice-refs
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "str",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "slice$<u8>",
+// wrapped-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "ref$<str>",
+// wrapped-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "ref$<slice$<u8> >",
+
+#![crate_type = "lib"]
+
+pub fn str_ref(x: &str) -> usize {
+    x.len()
+}
+
+pub fn slice_ref(x: &[u8]) -> usize {
+    x.len()
+}
@@ -0,0 +1,28 @@
+// Checks that references to `str` are only named `str$<str>` and `str_mut$<str>` in C++-like
+// debuginfo type names with `-Z debuginfo-names-wrap-str-refs`, and that references to
+// slices are not affected.
+
+// only-msvc
+// revisions: default wrapped
+// compile-flags: -Cdebuginfo=2 -Copt-level=0
+//[wrapped] compile-flags: -Zdebuginfo-names-wrap-str-refs
+
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "str",
+// default-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "slice$<u8>",
+// wrapped-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "str$<str>",
+// wrapped-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "str_mut$<str>",
+// wrapped-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "slice$<u8>",
+
+#![crate_type = "lib"]
+
+pub fn str_ref(x: &str) -> usize {
+    x.len()
+}
+
+pub fn str_mut(x: &mut str) -> usize {
+    x.len()
+}
+
+pub fn slice_ref(x: &[u8]) -> usize {
+    x.len()
+}
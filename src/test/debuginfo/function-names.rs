@@ -37,7 +37,12 @@
 // Const generic parameter
 // gdb-command:info functions -q function_names::const_generic_fn.*
 // gdb-check:[...]static fn function_names::const_generic_fn_bool<false>();
-// gdb-check:[...]static fn function_names::const_generic_fn_non_int<{CONST#3fcd7c34c1555be6}>();
+// gdb-check:[...]static fn function_names::const_generic_fn_char<'\n'>();
+// gdb-check:[...]static fn function_names::const_generic_fn_char<'x'>();
+// gdb-check:[...]static fn function_names::const_generic_fn_f32<inf>();
+// gdb-check:[...]static fn function_names::const_generic_fn_f64<-0.0>();
+// gdb-check:[...]static fn function_names::const_generic_fn_f64<3.14>();
+// gdb-check:[...]static fn function_names::const_generic_fn_non_int<{CONST#[...]}>();
 // gdb-check:[...]static fn function_names::const_generic_fn_signed_int<-7>();
 // gdb-check:[...]static fn function_names::const_generic_fn_unsigned_int<14>();
 
@@ -76,9 +81,14 @@
 // Const generic parameter
 // cdb-command:x a!function_names::const_generic_fn*
 // cdb-check:[...] a!function_names::const_generic_fn_bool<false> (void)
-// cdb-check:[...] a!function_names::const_generic_fn_non_int<CONST$3fcd7c34c1555be6> (void)
+// cdb-check:[...] a!function_names::const_generic_fn_char<120> (void)
+// cdb-check:[...] a!function_names::const_generic_fn_char<10> (void)
+// cdb-check:[...] a!function_names::const_generic_fn_f32<inf$> (void)
+// cdb-check:[...] a!function_names::const_generic_fn_f64<3.14> (void)
+// cdb-check:[...] a!function_names::const_generic_fn_f64<neg$0.0> (void)
+// cdb-check:[...] a!function_names::const_generic_fn_non_int<CONST$[...]> (void)
 // cdb-check:[...] a!function_names::const_generic_fn_unsigned_int<14> (void)
-// cdb-check:[...] a!function_names::const_generic_fn_signed_int<-7> (void)
+// cdb-check:[...] a!function_names::const_generic_fn_signed_int<neg$7> (void)
 
 #![allow(unused_variables)]
 #![feature(omit_gdb_pretty_printer_section)]
@@ -119,6 +129,11 @@ fn main() {
 
     // Const generic functions
     const_generic_fn_bool::<false>();
+    const_generic_fn_char::<'x'>();
+    const_generic_fn_char::<'\n'>();
+    const_generic_fn_f32::<{ f32::INFINITY }>();
+    const_generic_fn_f64::<3.14>();
+    const_generic_fn_f64::<{ -0.0 }>();
     const_generic_fn_non_int::<{ () }>();
     const_generic_fn_signed_int::<-7>();
     const_generic_fn_unsigned_int::<14>();
@@ -200,6 +215,9 @@ fn generic_func<T>(value: T) -> T {
 }
 
 fn const_generic_fn_bool<const C: bool>() {}
+fn const_generic_fn_char<const C: char>() {}
+fn const_generic_fn_f32<const C: f32>() {}
+fn const_generic_fn_f64<const C: f64>() {}
 fn const_generic_fn_non_int<const C: ()>() {}
 fn const_generic_fn_signed_int<const C: i64>() {}
 fn const_generic_fn_unsigned_int<const C: u32>() {}
@@ -0,0 +1,5 @@
+#![crate_type = "rlib"]
+
+extern crate foo;
+
+pub use foo::Type;
@@ -0,0 +1,10 @@
+extern crate bar;
+extern crate baz;
+
+fn both(types: (bar::Type, baz::Type)) -> (bar::Type, baz::Type) {
+    types
+}
+
+fn main() {
+    both((bar::Type, baz::Type));
+}
@@ -0,0 +1,73 @@
+#![feature(once_cell, rustc_private)]
+
+//! This program implements a rustc driver that counts how often the `debuginfo_type_name`
+//! query actually computes a name while the crate is compiled with debuginfo, as opposed to
+//! returning it from its cache, and prints how often the name of `Vec<HashMap<u32, String>>`
+//! was computed.
+
+extern crate rustc_codegen_ssa;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_codegen_ssa::debuginfo::type_names;
+use rustc_interface::interface::Config;
+use rustc_middle::ty::query::{ExternProviders, Providers};
+use rustc_middle::ty::{Ty, TyCtxt};
+use rustc_session::Session;
+use rustc_span::symbol::Symbol;
+use std::lazy::SyncLazy;
+use std::sync::Mutex;
+
+// The names computed by the `debuginfo_type_name` query, together with the `qualified` flag
+// they were computed for.
+static COMPUTED_NAMES: SyncLazy<Mutex<Vec<(String, bool)>>> = SyncLazy::new(Default::default);
+
+fn override_queries(_session: &Session, local: &mut Providers, _external: &mut ExternProviders) {
+    local.debuginfo_type_name = debuginfo_type_name;
+}
+
+fn debuginfo_type_name<'tcx>(tcx: TyCtxt<'tcx>, key: (Ty<'tcx>, bool)) -> Symbol {
+    let mut providers = Providers::default();
+    type_names::provide(&mut providers);
+    let name = (providers.debuginfo_type_name)(tcx, key);
+    COMPUTED_NAMES.lock().unwrap().push((name.to_string(), key.1));
+    name
+}
+
+fn main() {
+    let exit_code = rustc_driver::catch_with_exit_code(move || {
+        let rustc_args: Vec<_> = std::env::args().collect();
+        rustc_driver::RunCompiler::new(&rustc_args, &mut CompilerCalls).run()
+    });
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+
+    let computed_names = COMPUTED_NAMES.lock().unwrap().clone();
+
+    // The unqualified name is the name of the type description of the struct.
+    let vec_of_maps = computed_names
+        .iter()
+        .filter(|(name, qualified)| {
+            !qualified && name.starts_with("Vec<std::collections::hash::map::HashMap<u32,")
+        })
+        .count();
+    println!("Computations of the name of `Vec<HashMap<u32, String>>`: {}", vec_of_maps);
+
+    let mut distinct_names = computed_names.clone();
+    distinct_names.sort();
+    distinct_names.dedup();
+    assert_eq!(distinct_names.len(), computed_names.len(), "a name was computed more than once");
+}
+
+pub struct CompilerCalls;
+
+impl rustc_driver::Callbacks for CompilerCalls {
+    fn config(&mut self, config: &mut Config) {
+        assert!(config.override_queries.is_none());
+        config.override_queries = Some(override_queries);
+    }
+}
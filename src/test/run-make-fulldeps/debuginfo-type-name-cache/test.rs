@@ -0,0 +1,23 @@
+use std::collections::HashMap;
+
+// Every module ends up in a codegen unit of its own, each of which builds its own type
+// descriptions, so the name of `Vec<HashMap<u32, String>>` is asked for once per module.
+macro_rules! many_mods {
+    ($($name:ident)*) => {
+        $(
+            pub mod $name {
+                use super::HashMap;
+
+                pub fn f(x: &mut Vec<HashMap<u32, String>>) -> usize {
+                    let local: Vec<HashMap<u32, String>> = Vec::new();
+                    x.len() + local.len()
+                }
+            }
+        )*
+    }
+}
+
+many_mods! {
+    m00 m01 m02 m03 m04 m05 m06 m07
+    m08 m09 m10 m11 m12 m13 m14 m15
+}
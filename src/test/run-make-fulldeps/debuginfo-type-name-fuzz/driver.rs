@@ -0,0 +1,380 @@
+#![feature(rustc_private)]
+
+//! This program implements a rustc driver that builds types from short textual descriptions,
+//! names each of them in all styles, and checks that naming doesn't panic and that the names
+//! are well-formed. The descriptions come from `corpus.txt`, from a pseudo-random generator,
+//! and from mutating the corpus. The grammar of descriptions is
+//!
+//! ```text
+//! type := leaf | constructor "(" [type {"," type}] ")"
+//! leaf := "bool" | "char" | "u8" | "i128" | "f64" | "str" | "never" | "unit"
+//! ```
+//!
+//! with the constructors listed in `CONSTRUCTORS`. Whitespace between tokens is ignored.
+
+extern crate rustc_codegen_ssa;
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_span;
+extern crate rustc_target;
+
+use rustc_codegen_ssa::debuginfo::type_names::{
+    compute_debuginfo_type_name_for_style, DebuginfoNameStyle,
+};
+use rustc_driver::Compilation;
+use rustc_hir::def_id::DefId;
+use rustc_hir::Unsafety;
+use rustc_interface::interface::Compiler;
+use rustc_interface::Queries;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::symbol::{sym, Symbol};
+use rustc_target::spec::abi::Abi;
+use std::panic::AssertUnwindSafe;
+
+const CORPUS: &str = include_str!("corpus.txt");
+
+const LEAVES: &[&str] = &["bool", "char", "u8", "i128", "f64", "str", "never", "unit"];
+
+// The constructors of types, with the least and the most number of types they take:
+// - `wrapper(T)` and `pair(A, B)` are the structs from test.rs,
+// - `tuple(...)`, `slice(T)`, and `array(T)` (of length 3) are what they say,
+// - `ref(T)`, `mut(T)`, `ptr(T)`, and `ptr_mut(T)` are references and raw pointers,
+// - `fn(A, ..., R)` and `extern_fn(A, ..., R)` are function pointers returning their last
+//   type, with the Rust and the C ABI,
+// - `dyn(T)` is `&(dyn Tr<T> + Send)` and `iter(T)` is `&dyn Iterator<Item = T>`.
+const CONSTRUCTORS: &[(&str, usize, usize)] = &[
+    ("wrapper", 1, 1),
+    ("pair", 2, 2),
+    ("tuple", 0, 3),
+    ("slice", 1, 1),
+    ("array", 1, 1),
+    ("ref", 1, 1),
+    ("mut", 1, 1),
+    ("ptr", 1, 1),
+    ("ptr_mut", 1, 1),
+    ("fn", 1, 3),
+    ("extern_fn", 1, 3),
+    ("dyn", 1, 1),
+    ("iter", 1, 1),
+];
+
+// Descriptions nested deeper than this are rejected, so that arbitrary input can't overflow
+// the stack of the parser.
+const MAX_DEPTH: usize = 64;
+
+fn main() {
+    let exit_code = rustc_driver::catch_with_exit_code(move || {
+        let rustc_args: Vec<_> = std::env::args().collect();
+        rustc_driver::RunCompiler::new(&rustc_args, &mut CompilerCalls).run()
+    });
+    std::process::exit(exit_code);
+}
+
+pub struct CompilerCalls;
+
+impl rustc_driver::Callbacks for CompilerCalls {
+    fn after_analysis<'tcx>(
+        &mut self,
+        compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        compiler.session().abort_if_errors();
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            let find_item = |name| {
+                tcx.hir()
+                    .items()
+                    .find(|item| item.ident.name == Symbol::intern(name))
+                    .unwrap()
+                    .def_id
+                    .to_def_id()
+            };
+            let iterator = tcx.get_diagnostic_item(sym::Iterator).unwrap();
+            let items = Items {
+                wrapper: find_item("Wrapper"),
+                pair: find_item("Pair"),
+                tr: find_item("Tr"),
+                iterator,
+                item: tcx
+                    .associated_items(iterator)
+                    .in_definition_order()
+                    .find(|item| item.name == Symbol::intern("Item"))
+                    .unwrap()
+                    .def_id,
+                send: tcx.get_diagnostic_item(sym::Send).unwrap(),
+            };
+
+            // Every entry of the seed corpus describes a type.
+            let corpus: Vec<&str> = CORPUS.lines().filter(|line| !line.is_empty()).collect();
+            for entry in &corpus {
+                assert!(check_description(tcx, &items, entry.as_bytes()), "{}", entry);
+            }
+
+            // So does everything the generator comes up with.
+            let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+            let generated = 2000;
+            for _ in 0..generated {
+                let mut description = String::new();
+                generate(&mut rng, 6, &mut description);
+                assert!(check_description(tcx, &items, description.as_bytes()), "{}", description);
+            }
+
+            // Malformed descriptions are rejected rather than crashing the parser.
+            let malformed = ["", "wrapper(", "pair(u8)", "tuple(u8,", "u8 u8", "fn()", "(u8)"];
+            for description in malformed {
+                assert!(!check_description(tcx, &items, description.as_bytes()), "{}", description);
+            }
+            let deep =
+                format!("{}u8{}", "wrapper(".repeat(MAX_DEPTH + 1), ")".repeat(MAX_DEPTH + 1));
+            assert!(!check_description(tcx, &items, deep.as_bytes()));
+
+            // Mutated corpus entries may or may not describe a type.
+            let mut mutated = 0;
+            for entry in &corpus {
+                for _ in 0..20 {
+                    let mut bytes = entry.as_bytes().to_vec();
+                    let index = rng.below(bytes.len());
+                    bytes[index] = b"(),u8 "[rng.below(6)];
+                    check_description(tcx, &items, &bytes);
+                    mutated += 1;
+                }
+            }
+
+            println!(
+                "Checked {} corpus entries, {} generated descriptions, and {} mutated ones",
+                corpus.len(),
+                generated,
+                mutated
+            );
+        });
+
+        Compilation::Continue
+    }
+}
+
+// The items from test.rs and the library that types are built from.
+struct Items {
+    wrapper: DefId,
+    pair: DefId,
+    tr: DefId,
+    iterator: DefId,
+    item: DefId,
+    send: DefId,
+}
+
+// Builds the type described by `input`, names it in all styles, and checks the names.
+// Returns `false` if `input` doesn't describe a type. Any input is fine, so this can be
+// called with the input of a fuzzer.
+fn check_description<'tcx>(tcx: TyCtxt<'tcx>, items: &Items, input: &[u8]) -> bool {
+    let input = match std::str::from_utf8(input) {
+        Ok(input) => input,
+        Err(_) => return false,
+    };
+    let mut parser = Parser { tcx, items, input, pos: 0, depth: 0 };
+    let ty = match parser.parse_type() {
+        Some(ty) => ty,
+        None => return false,
+    };
+    parser.skip_whitespace();
+    if parser.pos != input.len() {
+        return false;
+    }
+
+    let styles =
+        [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust, DebuginfoNameStyle::GdbFriendly];
+    for style in styles {
+        // Rust-style names are also parsed back by the compiler itself, due to
+        // `-Z verify-debuginfo-type-names`.
+        let name = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            compute_debuginfo_type_name_for_style(tcx, ty, true, style)
+        }))
+        .unwrap_or_else(|_| panic!("naming `{}` in the {:?} style panicked", input, style));
+        check_brackets(&name, input);
+        if style == DebuginfoNameStyle::CppLike {
+            assert!(!name.contains(">>"), "`{}` for `{}`", name, input);
+        }
+    }
+    true
+}
+
+struct Parser<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    items: &'a Items,
+    input: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a, 'tcx> Parser<'a, 'tcx> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: char) -> bool {
+        self.skip_whitespace();
+        if self.input[self.pos..].starts_with(token) {
+            self.pos += token.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_type(&mut self) -> Option<Ty<'tcx>> {
+        self.skip_whitespace();
+        let rest = &self.input[self.pos..];
+        let len = rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(rest.len());
+        let word = &rest[..len];
+        self.pos += len;
+
+        if LEAVES.contains(&word) {
+            return Some(self.leaf(word));
+        }
+        let &(_, min, max) = CONSTRUCTORS.iter().find(|&&(name, ..)| name == word)?;
+        if self.depth == MAX_DEPTH || !self.eat('(') {
+            return None;
+        }
+        self.depth += 1;
+        let mut args = Vec::new();
+        while !self.eat(')') {
+            if !args.is_empty() && !self.eat(',') {
+                return None;
+            }
+            args.push(self.parse_type()?);
+        }
+        self.depth -= 1;
+        if args.len() < min || args.len() > max {
+            return None;
+        }
+        Some(self.construct(word, &args))
+    }
+
+    fn leaf(&self, word: &str) -> Ty<'tcx> {
+        let t = &self.tcx.types;
+        match word {
+            "bool" => t.bool,
+            "char" => t.char,
+            "u8" => t.u8,
+            "i128" => t.i128,
+            "f64" => t.f64,
+            "str" => t.str_,
+            "never" => t.never,
+            _ => self.tcx.mk_unit(),
+        }
+    }
+
+    fn construct(&self, word: &str, args: &[Ty<'tcx>]) -> Ty<'tcx> {
+        let tcx = self.tcx;
+        let re = tcx.lifetimes.re_erased;
+        let fn_ptr = |abi| {
+            let (&output, inputs) = args.split_last().unwrap();
+            let sig = tcx.mk_fn_sig(inputs.iter().copied(), output, false, Unsafety::Normal, abi);
+            tcx.mk_fn_ptr(ty::Binder::dummy(sig))
+        };
+        let dyn_ref = |predicates: [ty::ExistentialPredicate<'tcx>; 2]| {
+            let predicates = predicates.into_iter().map(ty::Binder::dummy);
+            tcx.mk_imm_ref(re, tcx.mk_dynamic(tcx.mk_poly_existential_predicates(predicates), re))
+        };
+        let substs = tcx.intern_substs(&args.iter().map(|&t| t.into()).collect::<Vec<_>>());
+
+        match word {
+            "wrapper" => tcx.mk_adt(tcx.adt_def(self.items.wrapper), substs),
+            "pair" => tcx.mk_adt(tcx.adt_def(self.items.pair), substs),
+            "tuple" => tcx.intern_tup(args),
+            "slice" => tcx.mk_slice(args[0]),
+            "array" => tcx.mk_array(args[0], 3),
+            "ref" => tcx.mk_imm_ref(re, args[0]),
+            "mut" => tcx.mk_mut_ref(re, args[0]),
+            "ptr" => tcx.mk_imm_ptr(args[0]),
+            "ptr_mut" => tcx.mk_mut_ptr(args[0]),
+            "fn" => fn_ptr(Abi::Rust),
+            "extern_fn" => fn_ptr(Abi::C { unwind: false }),
+            "dyn" => dyn_ref([
+                ty::ExistentialPredicate::Trait(ty::ExistentialTraitRef {
+                    def_id: self.items.tr,
+                    substs,
+                }),
+                ty::ExistentialPredicate::AutoTrait(self.items.send),
+            ]),
+            _ => {
+                let substs = ty::List::empty();
+                dyn_ref([
+                    ty::ExistentialPredicate::Trait(ty::ExistentialTraitRef {
+                        def_id: self.items.iterator,
+                        substs,
+                    }),
+                    ty::ExistentialPredicate::Projection(ty::ExistentialProjection {
+                        item_def_id: self.items.item,
+                        substs,
+                        term: args[0].into(),
+                    }),
+                ])
+            }
+        }
+    }
+}
+
+// A xorshift generator, so that every run checks the same descriptions.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+// Pushes the description of a random type that is nested at most `depth` levels deep.
+fn generate(rng: &mut Rng, depth: usize, output: &mut String) {
+    if depth == 0 || rng.below(4) == 0 {
+        output.push_str(LEAVES[rng.below(LEAVES.len())]);
+        return;
+    }
+    let (name, min, max) = CONSTRUCTORS[rng.below(CONSTRUCTORS.len())];
+    output.push_str(name);
+    output.push('(');
+    for i in 0..min + rng.below(max - min + 1) {
+        if i > 0 {
+            output.push_str(", ");
+        }
+        generate(rng, depth - 1, output);
+    }
+    output.push(')');
+}
+
+// Checks that all brackets in a name are closed in the right order. The `>` of the `->` in
+// function pointers is not a bracket.
+fn check_brackets(name: &str, description: &str) {
+    let mut open = Vec::new();
+    let mut prev = None;
+    for c in name.chars() {
+        match c {
+            '<' | '(' | '[' | '{' => open.push(c),
+            '>' if prev == Some('-') => {}
+            '>' | ')' | ']' | '}' => {
+                let expected = match c {
+                    '>' => '<',
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{',
+                };
+                let found = open.pop();
+                assert_eq!(
+                    found,
+                    Some(expected),
+                    "unbalanced `{}` in `{}` for `{}`",
+                    c,
+                    name,
+                    description
+                );
+            }
+            _ => {}
+        }
+        prev = Some(c);
+    }
+    assert!(open.is_empty(), "unclosed `{:?}` in `{}` for `{}`", open, name, description);
+}
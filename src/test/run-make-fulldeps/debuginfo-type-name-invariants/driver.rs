@@ -0,0 +1,202 @@
+#![feature(rustc_private)]
+
+//! This program implements a rustc driver that builds a matrix of types out of the kinds of
+//! types listed in `kinds`, names each of them in all styles, and checks that the names are
+//! well-formed. New kinds of types only need to be added to `kinds` to be tested in
+//! combination with all others.
+
+extern crate rustc_codegen_ssa;
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_span;
+extern crate rustc_target;
+
+use rustc_codegen_ssa::debuginfo::type_names::{
+    compute_debuginfo_type_name_for_style, name_is_cpp_like_style, DebuginfoNameStyle,
+};
+use rustc_driver::Compilation;
+use rustc_hir::def_id::DefId;
+use rustc_hir::Unsafety;
+use rustc_interface::interface::Compiler;
+use rustc_interface::Queries;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::symbol::{sym, Symbol};
+use rustc_target::spec::abi::Abi;
+
+fn main() {
+    let exit_code = rustc_driver::catch_with_exit_code(move || {
+        let rustc_args: Vec<_> = std::env::args().collect();
+        rustc_driver::RunCompiler::new(&rustc_args, &mut CompilerCalls).run()
+    });
+    std::process::exit(exit_code);
+}
+
+pub struct CompilerCalls;
+
+impl rustc_driver::Callbacks for CompilerCalls {
+    fn after_analysis<'tcx>(
+        &mut self,
+        compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        compiler.session().abort_if_errors();
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            let find_item = |name| {
+                tcx.hir()
+                    .items()
+                    .find(|item| item.ident.name == Symbol::intern(name))
+                    .unwrap()
+                    .def_id
+                    .to_def_id()
+            };
+            let items = Items {
+                wrapper: find_item("Wrapper"),
+                pair: find_item("Pair"),
+                tr: find_item("Tr"),
+                iterator: tcx.get_diagnostic_item(sym::Iterator).unwrap(),
+                send: tcx.get_diagnostic_item(sym::Send).unwrap(),
+            };
+
+            let t = &tcx.types;
+            let leaves = [t.bool, t.char, t.u8, t.i128, t.f64, t.str_, t.never, tcx.mk_unit()];
+            let kinds = kinds(tcx, &items);
+
+            // Every leaf wrapped in up to two kinds of types, in every order.
+            let mut types: Vec<Ty<'_>> = leaves.to_vec();
+            for &leaf in &leaves {
+                for outer in &kinds {
+                    types.push(outer(leaf));
+                    for inner in &kinds {
+                        types.push(outer(inner(leaf)));
+                    }
+                }
+            }
+
+            let styles = [
+                DebuginfoNameStyle::CppLike,
+                DebuginfoNameStyle::Rust,
+                DebuginfoNameStyle::GdbFriendly,
+            ];
+            for &ty in &types {
+                for style in styles {
+                    // Rust-style names are also parsed back by the compiler itself, due to
+                    // `-Z verify-debuginfo-type-names`.
+                    let name = compute_debuginfo_type_name_for_style(tcx, ty, true, style);
+                    check_brackets(&name);
+                    if style == DebuginfoNameStyle::CppLike {
+                        assert!(!name.contains(">>"), "{}", name);
+                    }
+                    // Rust-style names, like the `(T,)` of tuples of one, must not look
+                    // C++-like to `name_is_cpp_like_style`.
+                    if style == DebuginfoNameStyle::Rust {
+                        assert!(!name_is_cpp_like_style(&name), "{}", name);
+                    }
+                }
+            }
+
+            println!("Checked {} types of {} kinds", types.len(), kinds.len());
+        });
+
+        Compilation::Continue
+    }
+}
+
+// The items from test.rs and the library that the kinds of types are built from.
+struct Items {
+    wrapper: DefId,
+    pair: DefId,
+    tr: DefId,
+    iterator: DefId,
+    send: DefId,
+}
+
+type Kind<'tcx> = Box<dyn Fn(Ty<'tcx>) -> Ty<'tcx> + 'tcx>;
+
+fn kind<'tcx>(wrap: impl Fn(Ty<'tcx>) -> Ty<'tcx> + 'tcx) -> Kind<'tcx> {
+    Box::new(wrap)
+}
+
+// The kinds of types that the matrix is built from, each of which wraps a given type.
+fn kinds<'tcx>(tcx: TyCtxt<'tcx>, items: &Items) -> Vec<Kind<'tcx>> {
+    let re = tcx.lifetimes.re_erased;
+    let (wrapper, pair) = (tcx.adt_def(items.wrapper), tcx.adt_def(items.pair));
+    let (tr, iterator, send) = (items.tr, items.iterator, items.send);
+    let item = tcx
+        .associated_items(iterator)
+        .in_definition_order()
+        .find(|item| item.name == Symbol::intern("Item"))
+        .unwrap()
+        .def_id;
+    let fn_ptr = move |inputs: &[Ty<'tcx>], output: Ty<'tcx>, abi: Abi| {
+        let sig = tcx.mk_fn_sig(inputs.iter().copied(), output, false, Unsafety::Normal, abi);
+        tcx.mk_fn_ptr(ty::Binder::dummy(sig))
+    };
+    let dyn_ref = move |predicates: Vec<ty::ExistentialPredicate<'tcx>>| {
+        let predicates = predicates.into_iter().map(ty::Binder::dummy);
+        tcx.mk_imm_ref(re, tcx.mk_dynamic(tcx.mk_poly_existential_predicates(predicates), re))
+    };
+
+    vec![
+        // Structs with one and two generic parameters.
+        kind(move |t| tcx.mk_adt(wrapper, tcx.intern_substs(&[t.into()]))),
+        kind(move |t| tcx.mk_adt(pair, tcx.intern_substs(&[t.into(), t.into()]))),
+        // Tuples, slices, and arrays.
+        kind(move |t| tcx.intern_tup(&[t])),
+        kind(move |t| tcx.intern_tup(&[t, tcx.types.u8])),
+        kind(move |t| tcx.mk_slice(t)),
+        kind(move |t| tcx.mk_array(t, 3)),
+        // References and raw pointers.
+        kind(move |t| tcx.mk_imm_ref(re, t)),
+        kind(move |t| tcx.mk_mut_ref(re, t)),
+        kind(move |t| tcx.mk_imm_ptr(t)),
+        kind(move |t| tcx.mk_mut_ptr(t)),
+        // Function pointers, taking or returning the type.
+        kind(move |t| fn_ptr(&[t], tcx.mk_unit(), Abi::Rust)),
+        kind(move |t| fn_ptr(&[], t, Abi::C { unwind: false })),
+        // `&(dyn Tr<T> + Send)`.
+        kind(move |t| {
+            let tr = ty::ExistentialTraitRef { def_id: tr, substs: tcx.intern_substs(&[t.into()]) };
+            dyn_ref(vec![
+                ty::ExistentialPredicate::Trait(tr),
+                ty::ExistentialPredicate::AutoTrait(send),
+            ])
+        }),
+        // `&dyn Iterator<Item = T>`.
+        kind(move |t| {
+            let substs = ty::List::empty();
+            let iterator = ty::ExistentialTraitRef { def_id: iterator, substs };
+            let item = ty::ExistentialProjection { item_def_id: item, substs, term: t.into() };
+            dyn_ref(vec![
+                ty::ExistentialPredicate::Trait(iterator),
+                ty::ExistentialPredicate::Projection(item),
+            ])
+        }),
+    ]
+}
+
+// Checks that all brackets in a name are closed in the right order. The `>` of the `->` in
+// function pointers is not a bracket.
+fn check_brackets(name: &str) {
+    let mut open = Vec::new();
+    let mut prev = None;
+    for c in name.chars() {
+        match c {
+            '<' | '(' | '[' | '{' => open.push(c),
+            '>' if prev == Some('-') => {}
+            '>' | ')' | ']' | '}' => {
+                let expected = match c {
+                    '>' => '<',
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{',
+                };
+                assert_eq!(open.pop(), Some(expected), "unbalanced `{}` in {}", c, name);
+            }
+            _ => {}
+        }
+        prev = Some(c);
+    }
+    assert!(open.is_empty(), "unclosed `{:?}` in {}", open, name);
+}
@@ -0,0 +1,9 @@
+// The items that the driver builds its types from.
+
+pub struct Wrapper<T: ?Sized>(std::marker::PhantomData<T>);
+
+pub struct Pair<A: ?Sized, B: ?Sized>(std::marker::PhantomData<A>, std::marker::PhantomData<B>);
+
+pub trait Tr<T: ?Sized> {}
+
+fn main() {}
@@ -0,0 +1,27 @@
+// The niches of `low` and `high` are equally large, so the one that comes first in memory
+// holds the discriminant of `Tagged`, and `-Z randomize-layout` decides which one that is.
+
+pub enum Low {
+    A = 0,
+    B = 1,
+}
+
+pub enum High {
+    A = 5,
+    B = 6,
+}
+
+pub struct Fields {
+    pub low: Low,
+    pub high: High,
+    pub value: u32,
+}
+
+pub enum Tagged {
+    Data(Fields),
+    Empty,
+}
+
+pub fn tagged(x: Tagged) -> Tagged {
+    x
+}
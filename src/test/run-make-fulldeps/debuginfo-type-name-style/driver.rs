@@ -0,0 +1,811 @@
+#![feature(rustc_private)]
+
+//! This program implements a rustc driver that prints the debuginfo type names of the
+//! parameters of the `names`, `lifetimes`, and `dyn_lifetimes` functions in both styles
+//! supported by `compute_debuginfo_type_name_for_style`, as well as the short names of
+//! the parameters of `short_names` and a number of other properties (see the Makefile).
+
+extern crate rustc_codegen_ssa;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_span;
+
+use rustc_codegen_ssa::debuginfo::type_names::{
+    collect_type_name_defids, collect_type_name_defids_with_options,
+    compute_debuginfo_dyn_pointer_names, compute_debuginfo_field_type_names,
+    compute_debuginfo_type_name, compute_debuginfo_type_name_and_id,
+    compute_debuginfo_type_name_classified, compute_debuginfo_type_name_for_style,
+    compute_debuginfo_type_name_relative_to, compute_debuginfo_type_name_symbol,
+    compute_debuginfo_type_name_with_emitter, compute_debuginfo_type_name_with_options,
+    compute_debuginfo_type_names, debuginfo_type_name_capacity_hint, enum_niche_range,
+    name_is_cpp_like_style, parse_debuginfo_type_name, push_cpp_like_escaped_name,
+    push_generic_params, push_generic_params_with_visited, push_short_type_name, separators,
+    write_debuginfo_type_name, DebuginfoNameCx, DebuginfoNameEmitter, DebuginfoNameStyle,
+    DebuginfoTypeNameOptions, DefaultDebuginfoNameEmitter, NeverTypeName, PathQualification,
+    TypeNameShape, VisitedTypes,
+};
+use rustc_driver::Compilation;
+use rustc_interface::interface::Compiler;
+use rustc_interface::Queries;
+use rustc_middle::ty::{self, ParamEnv, Ty, TyVid};
+use rustc_span::symbol::{sym, Symbol};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Keeps track of the number of bytes allocated so far, for measuring how much memory
+// computing debuginfo type names takes.
+struct CountingAllocator;
+
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let exit_code = rustc_driver::catch_with_exit_code(move || {
+        let rustc_args: Vec<_> = std::env::args().collect();
+        rustc_driver::RunCompiler::new(&rustc_args, &mut CompilerCalls).run()
+    });
+    std::process::exit(exit_code);
+}
+
+pub struct CompilerCalls;
+
+impl rustc_driver::Callbacks for CompilerCalls {
+    fn after_analysis<'tcx>(
+        &mut self,
+        compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        compiler.session().abort_if_errors();
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            let find_item = |name| {
+                tcx.hir()
+                    .items()
+                    .find(|item| item.ident.name == Symbol::intern(name))
+                    .unwrap()
+                    .def_id
+                    .to_def_id()
+            };
+
+            // Late-bound regions are erased here, just like during codegen.
+            let def_id = find_item("names");
+            let names_sig = tcx.erase_late_bound_regions(tcx.fn_sig(def_id));
+            // Here they are kept, so that their names can show up in type names.
+            let def_id = find_item("lifetimes");
+            let lifetimes_sig = tcx.liberate_late_bound_regions(def_id, tcx.fn_sig(def_id));
+            let def_id = find_item("dyn_lifetimes");
+            let dyn_lifetimes_sig = tcx.liberate_late_bound_regions(def_id, tcx.fn_sig(def_id));
+
+            for &ty in names_sig
+                .inputs()
+                .iter()
+                .chain(lifetimes_sig.inputs())
+                .chain(dyn_lifetimes_sig.inputs())
+            {
+                let names = [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust]
+                    .map(|style| compute_debuginfo_type_name_for_style(tcx, ty, true, style));
+                println!("CppLike: {}", names[0]);
+                println!("Rust: {}", names[1]);
+
+                // `name_is_cpp_like_style` has to recognize C++-like names unless they are
+                // also valid Rust-style names (like `str` for `&str`), and must never
+                // mistake a Rust-style name for a C++-like one.
+                let cpp_like_is_ambiguous = parse_debuginfo_type_name(&names[0]).is_ok();
+                assert_eq!(
+                    name_is_cpp_like_style(&names[0]),
+                    !cpp_like_is_ambiguous,
+                    "{}",
+                    names[0]
+                );
+                assert!(!name_is_cpp_like_style(&names[1]), "{}", names[1]);
+            }
+
+            // The same goes for names with a trailing comma, a comma or `$` inside of a
+            // constant, or commas without spaces due to `-Z debuginfo-names-dwarf-safe`.
+            let dwarf_safe = DebuginfoTypeNameOptions {
+                style: DebuginfoNameStyle::Rust,
+                dwarf_safe: true,
+                ..DebuginfoTypeNameOptions::new(tcx, true)
+            };
+            let def_id = find_item("tricky_styles");
+            for &ty in tcx.erase_late_bound_regions(tcx.fn_sig(def_id)).inputs() {
+                let names = [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust]
+                    .map(|style| compute_debuginfo_type_name_for_style(tcx, ty, true, style));
+                let cpp_like_is_ambiguous = parse_debuginfo_type_name(&names[0]).is_ok();
+                assert_eq!(
+                    name_is_cpp_like_style(&names[0]),
+                    !cpp_like_is_ambiguous,
+                    "{}",
+                    names[0]
+                );
+                assert!(!name_is_cpp_like_style(&names[1]), "{}", names[1]);
+                let name = compute_debuginfo_type_name_with_options(tcx, ty, &dwarf_safe);
+                assert!(!name_is_cpp_like_style(&name), "{}", name);
+            }
+
+            // Writing the name through `fmt::Write` has to produce the same bytes as
+            // computing it as a `String`, also for primitive types, which are written directly.
+            // Computing it as a `Symbol` interns it only once.
+            for ty in names_sig.inputs().iter().copied().chain([tcx.types.u32]) {
+                let mut written = ByteSink::default();
+                write_debuginfo_type_name(tcx, ty, true, &mut written).unwrap();
+                assert_eq!(written.0, compute_debuginfo_type_name(tcx, ty, true).into_bytes());
+                let symbol = compute_debuginfo_type_name_symbol(tcx, ty, true);
+                assert_eq!(symbol, compute_debuginfo_type_name_symbol(tcx, ty, true));
+                assert_eq!(symbol.as_str(), compute_debuginfo_type_name(tcx, ty, true));
+            }
+
+            // Naming many types at once has to give the same names as naming them one by one.
+            let mixed_types: Vec<Ty<'_>> = names_sig
+                .inputs()
+                .iter()
+                .copied()
+                .chain([tcx.types.u8, tcx.intern_tup(&[tcx.types.u8, tcx.types.u8]), tcx.types.u8])
+                .collect();
+            let batch_names = compute_debuginfo_type_names(tcx, &mixed_types, true);
+            let single_names: Vec<_> =
+                mixed_types.iter().map(|&ty| compute_debuginfo_type_name(tcx, ty, true)).collect();
+            assert_eq!(batch_names, single_names);
+
+            // Trait objects are parenthesized like in diagnostics, i.e. only if they have a
+            // lifetime bound.
+            let def_id = find_item("dyn_parens");
+            let dyn_parens_sig = tcx.erase_late_bound_regions(tcx.fn_sig(def_id));
+            for &ty in dyn_parens_sig.inputs().iter().chain(dyn_lifetimes_sig.inputs()) {
+                let name =
+                    compute_debuginfo_type_name_for_style(tcx, ty, true, DebuginfoNameStyle::Rust);
+                assert_eq!(name.contains("(dyn "), ty.to_string().contains("(dyn "), "{}", name);
+                println!("Rust: {}", name);
+            }
+
+            let def_id = find_item("short_names");
+            let short_names_sig = tcx.erase_late_bound_regions(tcx.fn_sig(def_id));
+            for &ty in short_names_sig.inputs() {
+                let mut name = String::new();
+                push_short_type_name(tcx, ty, &mut name);
+                println!("Short: {}", name);
+                // The short name is the end of the full name, not a name of its own.
+                assert_eq!(name, compute_debuginfo_type_name(tcx, ty, false));
+                assert!(compute_debuginfo_type_name(tcx, ty, true).ends_with(&name), "{}", name);
+            }
+
+            // A custom emitter overrides the names of nested types as well.
+            for &ty in [names_sig.inputs()[0], short_names_sig.inputs()[2]].iter() {
+                for style in [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust] {
+                    let opts = DebuginfoTypeNameOptions {
+                        style,
+                        ..DebuginfoTypeNameOptions::new(tcx, true)
+                    };
+                    let name =
+                        compute_debuginfo_type_name_with_emitter(tcx, ty, &opts, &ByteEmitter);
+                    println!("{:?} (custom emitter): {}", style, name);
+                }
+            }
+
+            // The niche range of an enum has to match what ends up in its C++-like name.
+            let def_id = find_item("niche");
+            let ty = tcx.erase_late_bound_regions(tcx.fn_sig(def_id)).inputs()[0];
+            let (min, max, dataful_variant) = enum_niche_range(tcx, ty).unwrap();
+            let name =
+                compute_debuginfo_type_name_for_style(tcx, ty, true, DebuginfoNameStyle::CppLike);
+            println!("Niche range: {}..={} ({})", min, max, dataful_variant);
+            println!("CppLike: {}", name);
+            assert!(name.ends_with(&format!(", {}, {}, {}>", min, max, dataful_variant)));
+
+            // Enums whose layout can't be computed still get a name, just without the niche
+            // range or variant.
+            let ty = tcx.type_of(find_item("TooBig"));
+            assert!(tcx.layout_of(ParamEnv::reveal_all().and(ty)).is_err());
+            assert_eq!(enum_niche_range(tcx, ty), None);
+            let name =
+                compute_debuginfo_type_name_for_style(tcx, ty, true, DebuginfoNameStyle::CppLike);
+            println!("CppLike (no layout): {}", name);
+
+            // Opaque types are named after their bounds with `-Z debuginfo-names-fallback`.
+            let def_id = find_item("opaque");
+            let ty = tcx.erase_late_bound_regions(tcx.fn_sig(def_id)).output();
+            for style in [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust] {
+                let name = compute_debuginfo_type_name_for_style(tcx, ty, true, style);
+                println!("{:?}: {}", style, name);
+            }
+
+            // The bounds of opaque types are in source order, unless names are canonical.
+            let debug_send = find_item("opaque_debug_send");
+            let send_debug = find_item("opaque_send_debug");
+            for canonical in [false, true] {
+                let opts = DebuginfoTypeNameOptions {
+                    style: DebuginfoNameStyle::Rust,
+                    canonical,
+                    ..DebuginfoTypeNameOptions::new(tcx, true)
+                };
+                let names = [debug_send, send_debug].map(|def_id| {
+                    let ty = tcx.erase_late_bound_regions(tcx.fn_sig(def_id)).output();
+                    compute_debuginfo_type_name_with_options(tcx, ty, &opts)
+                });
+                println!("Rust (canonical: {}): {}", canonical, names[0]);
+                println!("Rust (canonical: {}): {}", canonical, names[1]);
+                assert_eq!(canonical, names[0] == names[1]);
+            }
+
+            // The associated item bindings of trait objects keep the order of the trait
+            // object's predicates, unless names are canonical.
+            let def_id = find_item("binding_order");
+            let ty = tcx.erase_late_bound_regions(tcx.fn_sig(def_id)).inputs()[0];
+            let predicates = match *ty.builtin_deref(true).unwrap().ty.kind() {
+                ty::Dynamic(predicates, _) => predicates,
+                _ => unreachable!(),
+            };
+            let bindings: Vec<_> = predicates
+                .projection_bounds()
+                .map(|bound| {
+                    let bound = bound.skip_binder();
+                    format!("{}={}", tcx.item_name(bound.item_def_id), bound.term.ty().unwrap())
+                })
+                .collect();
+            for canonical in [false, true] {
+                let opts = DebuginfoTypeNameOptions {
+                    style: DebuginfoNameStyle::Rust,
+                    canonical,
+                    ..DebuginfoTypeNameOptions::new(tcx, true)
+                };
+                let name = compute_debuginfo_type_name_with_options(tcx, ty, &opts);
+                let expected = if canonical {
+                    "&dyn test::Bindings<First=u8, Second=u16>".to_owned()
+                } else {
+                    format!("&dyn test::Bindings<{}>", bindings.join(", "))
+                };
+                assert_eq!(name, expected);
+            }
+
+            // Types that should never reach codegen get a placeholder name with
+            // `-Z debuginfo-names-fallback`.
+            let ty = tcx.mk_ty_var(TyVid::from_u32(0));
+            for style in [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust] {
+                let name = compute_debuginfo_type_name_for_style(tcx, ty, true, style);
+                println!("{:?}: {}", style, name);
+            }
+            // Projections are normalized away before codegen, unlike opaque types, which
+            // are named after their bounds (see above).
+            let def_id = find_item("projection");
+            let ty = tcx.erase_late_bound_regions(tcx.fn_sig(def_id)).inputs()[0];
+            for style in [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust] {
+                let name = compute_debuginfo_type_name_for_style(tcx, ty, true, style);
+                println!("{:?}: {}", style, name);
+            }
+            // So does `ty::Error`. Constructing it registers a delayed bug, which is dropped
+            // again because there is no actual error to report here.
+            let ty = tcx.ty_error();
+            let names = [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust]
+                .map(|style| compute_debuginfo_type_name_for_style(tcx, ty, true, style));
+            tcx.sess.diagnostic().reset_err_count();
+            assert_eq!(names, ["unknown$", "<unknown:Error>"]);
+            println!("CppLike: {}", names[0]);
+            println!("Rust: {}", names[1]);
+
+            // Trait objects can't bind associated consts in source yet (that needs
+            // `associated_const_equality`, and such traits aren't object safe), so the const
+            // term of a projection bound is built by hand here.
+            let trait_def_id = find_item("WithLen");
+            let item_def_id =
+                tcx.associated_items(trait_def_id).in_definition_order().next().unwrap().def_id;
+            let principal = ty::ExistentialPredicate::Trait(ty::ExistentialTraitRef {
+                def_id: trait_def_id,
+                substs: ty::List::empty(),
+            });
+            let projection = ty::ExistentialPredicate::Projection(ty::ExistentialProjection {
+                item_def_id,
+                substs: ty::List::empty(),
+                term: ty::Term::Const(ty::Const::from_usize(tcx, 3)),
+            });
+            let predicates = tcx.mk_poly_existential_predicates(
+                [principal, projection].into_iter().map(ty::Binder::dummy),
+            );
+            let ty = tcx.mk_imm_ref(
+                tcx.lifetimes.re_erased,
+                tcx.mk_dynamic(predicates, tcx.lifetimes.re_erased),
+            );
+            let names = [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust]
+                .map(|style| compute_debuginfo_type_name_for_style(tcx, ty, true, style));
+            assert_eq!(
+                names,
+                ["ref$<dyn$<test::WithLen<assoc$<LEN,3> > > >", "&dyn test::WithLen<LEN=3>"]
+            );
+            println!("CppLike: {}", names[0]);
+            println!("Rust: {}", names[1]);
+
+            // A type parameter and a const parameter with the same name.
+            let ty = tcx.type_of(find_item("Colliding"));
+            for style in [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust] {
+                let name = compute_debuginfo_type_name_for_style(tcx, ty, true, style);
+                println!("{:?}: {}", style, name);
+            }
+
+            // Array lengths that are constant expressions involving generic parameters can't
+            // be evaluated, so they are named by their hash (which is masked here).
+            let def_id = find_item("array_lengths");
+            for &ty in tcx.erase_late_bound_regions(tcx.fn_sig(def_id)).inputs() {
+                for style in [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust] {
+                    let name = compute_debuginfo_type_name_for_style(tcx, ty, true, style);
+                    println!("{:?}: {}", style, mask_const_hashes(&name));
+                }
+            }
+
+            // Item names with characters that MSVC's expression parser can't deal with are
+            // escaped in C++-like names.
+            for name in ["plain_name", "weird#name", "{weird}", "$23"] {
+                let mut escaped = String::new();
+                push_cpp_like_escaped_name(name, &mut escaped);
+                println!("Escaped: {} -> {}", name, escaped);
+            }
+
+            // The path of a nested item at every level of qualification.
+            let ty = tcx.type_of(find_item("Nested"));
+            for path_qualification in
+                [PathQualification::Full, PathQualification::CrateRelative, PathQualification::None]
+            {
+                let opts = DebuginfoTypeNameOptions {
+                    path_qualification,
+                    ..DebuginfoTypeNameOptions::new(tcx, true)
+                };
+                let name = compute_debuginfo_type_name_with_options(tcx, ty, &opts);
+                println!("{:?}: {}", path_qualification, name);
+            }
+
+            // A `&dyn Display` and the names of the vtables it points to for two different
+            // concrete types.
+            let def_id = find_item("vtables");
+            let vtables_sig = tcx.erase_late_bound_regions(tcx.fn_sig(def_id));
+            let (dyn_ref_ty, concrete_tys) = vtables_sig.inputs().split_first().unwrap();
+            for &concrete_ty in concrete_tys {
+                let (value_name, vtable_name) =
+                    compute_debuginfo_dyn_pointer_names(tcx, *dyn_ref_ty, concrete_ty).unwrap();
+                println!("Value: {}, vtable: {}", value_name, vtable_name);
+            }
+            // A `u32` is not a pointer to a trait object.
+            assert_eq!(
+                compute_debuginfo_dyn_pointer_names(tcx, concrete_tys[0], *dyn_ref_ty),
+                None
+            );
+
+            // Every kind of type that can reach codegen gets a name of the matching shape.
+            let def_id = find_item("shapes");
+            let shapes_sig = tcx.erase_late_bound_regions(tcx.fn_sig(def_id));
+            let inputs = shapes_sig.inputs();
+            let closure_def_id = tcx
+                .hir()
+                .body_owners()
+                .map(|def_id| def_id.to_def_id())
+                .find(|&def_id| tcx.is_closure(def_id))
+                .unwrap();
+            let opaque_ty = tcx.fn_sig(find_item("opaque")).skip_binder().output();
+            let shapes = [
+                (tcx.types.u8, TypeNameShape::Primitive),
+                (tcx.types.never, TypeNameShape::Primitive),
+                (inputs[0], TypeNameShape::Param),
+                (inputs[1], TypeNameShape::Adt),
+                (inputs[2], TypeNameShape::Tuple),
+                (tcx.mk_unit(), TypeNameShape::Tuple),
+                (inputs[3], TypeNameShape::Array),
+                (inputs[4], TypeNameShape::Pointer),
+                (inputs[4].builtin_deref(true).unwrap().ty, TypeNameShape::Slice),
+                (inputs[5], TypeNameShape::Pointer),
+                (inputs[5].builtin_deref(true).unwrap().ty, TypeNameShape::Dyn),
+                (inputs[6], TypeNameShape::FnPtr),
+                (tcx.type_of(def_id), TypeNameShape::FnDef),
+                (tcx.type_of(closure_def_id), TypeNameShape::Closure),
+                (opaque_ty, TypeNameShape::Opaque),
+                (tcx.mk_ty_var(TyVid::from_u32(0)), TypeNameShape::Other),
+            ];
+            for (ty, expected_shape) in shapes {
+                let (name, shape) = compute_debuginfo_type_name_classified(tcx, ty, true);
+                assert_eq!(shape, expected_shape, "{}", name);
+                assert_eq!(name, compute_debuginfo_type_name(tcx, ty, true));
+            }
+
+            // Closures named after where they are defined, for tools that show names to users.
+            let ty = tcx.type_of(closure_def_id);
+            for closure_locations in [false, true] {
+                let opts = DebuginfoTypeNameOptions {
+                    style: DebuginfoNameStyle::Rust,
+                    closure_locations,
+                    ..DebuginfoTypeNameOptions::new(tcx, true)
+                };
+                let name = compute_debuginfo_type_name_with_options(tcx, ty, &opts);
+                println!("Rust (closure locations: {}): {}", closure_locations, name);
+            }
+
+            // `impl Trait` gets the same name in argument position, where it is a type
+            // parameter, as in return position, where it is an opaque type, if the function it
+            // belongs to is known.
+            let def_id = find_item("impl_traits");
+            let impl_traits_sig = tcx.fn_sig(def_id).skip_binder();
+            let (input_ty, output_ty) = (impl_traits_sig.inputs()[0], impl_traits_sig.output());
+            let name = compute_debuginfo_type_name(tcx, input_ty, true);
+            println!("Rust (no generics owner): {}", name);
+            for style in [DebuginfoNameStyle::Rust, DebuginfoNameStyle::CppLike] {
+                let opts = DebuginfoTypeNameOptions {
+                    style,
+                    generics_owner: Some(def_id),
+                    ..DebuginfoTypeNameOptions::new(tcx, true)
+                };
+                let name = compute_debuginfo_type_name_with_options(tcx, input_ty, &opts);
+                let output_name = compute_debuginfo_type_name_with_options(tcx, output_ty, &opts);
+                assert_eq!(name, output_name);
+                println!("{:?}: {}", style, name);
+            }
+
+            // The never type can be named the same in all styles.
+            let never_type_names = [
+                NeverTypeName::ForStyle,
+                NeverTypeName::Exclamation,
+                NeverTypeName::CppLike,
+                NeverTypeName::Word,
+            ];
+            for never_type_name in never_type_names {
+                for style in [DebuginfoNameStyle::Rust, DebuginfoNameStyle::CppLike] {
+                    let opts = DebuginfoTypeNameOptions {
+                        style,
+                        never_type_name,
+                        ..DebuginfoTypeNameOptions::new(tcx, true)
+                    };
+                    let name =
+                        compute_debuginfo_type_name_with_options(tcx, tcx.types.never, &opts);
+                    println!("{:?} ({:?}): {}", style, never_type_name, name);
+                }
+            }
+
+            // Naming generic arguments part by part with a shared visited set gives the same
+            // names as naming each part on its own.
+            let mut visited = VisitedTypes::default();
+            let (mut shared, mut fresh) = (String::new(), String::new());
+            let substs = [
+                tcx.intern_substs(&[inputs[1].into()]),
+                tcx.intern_substs(&[inputs[2].into(), inputs[3].into()]),
+            ];
+            for substs in substs {
+                push_generic_params_with_visited(tcx, substs, &mut shared, &mut visited);
+                push_generic_params(tcx, substs, &mut fresh);
+            }
+            assert_eq!(shared, fresh);
+            println!("Shared visited set: {}", shared);
+
+            // A type that shows up inside of itself is named as a recursive type, both when the
+            // caller has already started the walk over it...
+            let vec_u8 = names_sig.inputs()[0];
+            let mut visited = VisitedTypes::default();
+            let mut name = String::new();
+            assert!(visited.enter(vec_u8));
+            let substs = tcx.intern_substs(&[vec_u8.into()]);
+            push_generic_params_with_visited(tcx, substs, &mut name, &mut visited);
+            visited.exit(vec_u8);
+            assert_eq!(name, "<<recursive_type>>");
+            println!("Recursive: {}", name);
+            // ... and when an emitter names a type after itself.
+            for style in [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust] {
+                let opts =
+                    DebuginfoTypeNameOptions { style, ..DebuginfoTypeNameOptions::new(tcx, true) };
+                let name =
+                    compute_debuginfo_type_name_with_emitter(tcx, vec_u8, &opts, &CyclicEmitter);
+                println!("{:?} (cyclic emitter): {}", style, name);
+            }
+            // The same type next to itself is not recursive, neither for ADTs nor for tuples.
+            let def_id = find_item("siblings");
+            for &ty in tcx.fn_sig(def_id).skip_binder().inputs() {
+                for style in [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust] {
+                    let name = compute_debuginfo_type_name_for_style(tcx, ty, true, style);
+                    assert!(!name.contains("recursive_type"), "{}", name);
+                    println!("{:?}: {}", style, name);
+                }
+            }
+
+            // Higher-ranked function pointers keep their named lifetimes and their binder.
+            let def_id = find_item("higher_ranked");
+            for &ty in tcx.fn_sig(def_id).skip_binder().inputs() {
+                for lifetimes in [false, true] {
+                    let opts = DebuginfoTypeNameOptions {
+                        style: DebuginfoNameStyle::Rust,
+                        lifetimes,
+                        ..DebuginfoTypeNameOptions::new(tcx, true)
+                    };
+                    let name = compute_debuginfo_type_name_with_options(tcx, ty, &opts);
+                    println!("Rust (lifetimes: {}): {}", lifetimes, name);
+                }
+            }
+
+            // Types get the same id if and only if they are the same type, no matter where the
+            // type comes from.
+            let types =
+                [inputs[1], inputs[2], inputs[3], inputs[4], inputs[6], names_sig.inputs()[1]];
+            for (i, &a) in types.iter().enumerate() {
+                for (j, &b) in types.iter().enumerate() {
+                    let (name_a, id_a) = compute_debuginfo_type_name_and_id(tcx, a, true);
+                    let (name_b, id_b) = compute_debuginfo_type_name_and_id(tcx, b, true);
+                    assert_eq!(i == j, id_a == id_b, "{} vs. {}", name_a, name_b);
+                }
+            }
+            let other_tuple = tcx.fn_sig(find_item("other_tuple")).skip_binder().inputs()[0];
+            assert_eq!(
+                compute_debuginfo_type_name_and_id(tcx, other_tuple, true),
+                compute_debuginfo_type_name_and_id(tcx, inputs[2], true),
+            );
+
+            // Names assembled by hand with the separator helpers are the same as the ones the
+            // compiler computes. Auto traits come in the order of the `DefPathHash` of the
+            // trait, not in the order of their names.
+            let vec_ty = names_sig.inputs()[0];
+            let def_id = find_item("auto_traits");
+            let dyn_ty = tcx.erase_late_bound_regions(tcx.fn_sig(def_id)).inputs()[0];
+            let mut auto_traits = [
+                (tcx.get_diagnostic_item(sym::Send).unwrap(), "core::marker::Send"),
+                (tcx.lang_items().sync_trait().unwrap(), "core::marker::Sync"),
+                (tcx.lang_items().unpin_trait().unwrap(), "core::marker::Unpin"),
+            ];
+            auto_traits.sort_by_key(|&(def_id, _)| tcx.def_path_hash(def_id));
+            for style in [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust] {
+                let cpp_like = style == DebuginfoNameStyle::CppLike;
+
+                let mut name = String::from("alloc::vec::Vec<u8");
+                separators::push_arg_separator(cpp_like, false, &mut name);
+                name.push_str("alloc::alloc::Global");
+                separators::push_close_angle_bracket(cpp_like, &mut name);
+                assert_eq!(name, compute_debuginfo_type_name_for_style(tcx, vec_ty, true, style));
+
+                let mut name = String::from(if cpp_like { "ref$<dyn$<" } else { "&dyn " });
+                for (_, auto_trait) in auto_traits {
+                    name.push_str(auto_trait);
+                    separators::push_auto_trait_separator(cpp_like, false, &mut name);
+                }
+                separators::pop_auto_trait_separator(&mut name);
+                if cpp_like {
+                    separators::push_close_angle_bracket(cpp_like, &mut name);
+                    separators::push_close_angle_bracket(cpp_like, &mut name);
+                }
+                assert_eq!(name, compute_debuginfo_type_name_for_style(tcx, dyn_ty, true, style));
+            }
+
+            // Popping a separator that isn't there is a bug, which debug builds of the compiler
+            // catch. Release builds leave the name alone instead of crashing.
+            let mut name = String::from("alloc::vec::Vec<u8");
+            let popped = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                separators::pop_arg_separator(&mut name);
+                separators::pop_auto_trait_separator(&mut name);
+            }));
+            if popped.is_ok() {
+                assert_eq!(name, "alloc::vec::Vec<u8");
+            }
+
+            // Type parameters are named like in the source code.
+            let def_id = find_item("params");
+            for &ty in tcx.fn_sig(def_id).skip_binder().inputs() {
+                let names = [DebuginfoNameStyle::Rust, DebuginfoNameStyle::CppLike]
+                    .map(|style| compute_debuginfo_type_name_for_style(tcx, ty, true, style));
+                println!("Rust: {}", names[0]);
+                println!("CppLike: {}", names[1]);
+            }
+
+            // Field types are named in the instantiation of the struct they belong to.
+            let def_id = find_item("fields");
+            let ty = tcx.fn_sig(def_id).skip_binder().inputs()[0];
+            for (field, name) in compute_debuginfo_field_type_names(tcx, ty) {
+                println!("Field {}: {}", field, name);
+            }
+
+            // The items a name refers to are reported in the order they appear in the name.
+            // Type parameters are reported if it is known which item they belong to.
+            let def_id = find_item("defids");
+            let ty = tcx.fn_sig(def_id).skip_binder().inputs()[0];
+            let opts = DebuginfoTypeNameOptions {
+                generics_owner: Some(def_id),
+                ..DebuginfoTypeNameOptions::new(tcx, true)
+            };
+            let def_ids = collect_type_name_defids_with_options(tcx, ty, &opts);
+            let params: Vec<_> = tcx.generics_of(def_id).params.iter().map(|p| p.def_id).collect();
+            assert_eq!(def_ids[0], tcx.get_diagnostic_item(sym::Vec).unwrap());
+            assert_eq!(def_ids[1], tcx.get_diagnostic_item(sym::HashMap).unwrap());
+            assert_eq!(def_ids[2..4], params[..]);
+            let without_params = collect_type_name_defids(tcx, ty);
+            assert!(without_params.iter().all(|def_id| !params.contains(def_id)));
+            let names: Vec<_> =
+                def_ids.iter().map(|&def_id| tcx.item_name(def_id).to_string()).collect();
+            println!("Items: {}", names.join(", "));
+
+            // Names relative to a module leave out its path from the items inside of it, and only
+            // from those.
+            let def_id = find_item("relative");
+            let ty = tcx.fn_sig(def_id).skip_binder().inputs()[0];
+            let geometry = find_item("geometry");
+            println!("Relative: {}", compute_debuginfo_type_name_relative_to(tcx, ty, geometry));
+
+            // The name cached by the `debuginfo_type_name` query is the same as the uncached
+            // one, byte for byte. That codegen computes each name only once is checked by the
+            // `debuginfo-type-name-cache` test.
+            let def_id = find_item("cached");
+            let ty = tcx.fn_sig(def_id).skip_binder().inputs()[0];
+            for qualified in [true, false] {
+                let opts = DebuginfoTypeNameOptions::new(tcx, qualified);
+                let uncached = compute_debuginfo_type_name_with_emitter(
+                    tcx,
+                    ty,
+                    &opts,
+                    &DefaultDebuginfoNameEmitter,
+                );
+                let cached = compute_debuginfo_type_name_symbol(tcx, ty, qualified);
+                assert_eq!(cached.as_str().as_bytes(), uncached.as_bytes());
+            }
+
+            // Looking up a cached `Symbol` takes less memory than computing a `String`, which
+            // allocates the name every time but doesn't intern it for the rest of the session.
+            let before = ALLOCATED_BYTES.load(Ordering::Relaxed);
+            for _ in 0..100 {
+                compute_debuginfo_type_name_symbol(tcx, ty, true);
+            }
+            let between = ALLOCATED_BYTES.load(Ordering::Relaxed);
+            for _ in 0..100 {
+                compute_debuginfo_type_name(tcx, ty, true);
+            }
+            let after = ALLOCATED_BYTES.load(Ordering::Relaxed);
+            assert!(between - before < after - between);
+            println!("Cached names allocate less than uncached ones: pass");
+
+            // The options of the session give the same names as the functions without options,
+            // for ADTs, tuples, references, trait objects, and function pointers alike.
+            let def_id = find_item("default_options");
+            for &ty in tcx.erase_late_bound_regions(tcx.fn_sig(def_id)).inputs() {
+                for style in [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust] {
+                    let opts = DebuginfoTypeNameOptions {
+                        style,
+                        ..DebuginfoTypeNameOptions::new(tcx, true)
+                    };
+                    let name = compute_debuginfo_type_name_with_options(tcx, ty, &opts);
+                    assert_eq!(name, compute_debuginfo_type_name_for_style(tcx, ty, true, style));
+                    let emitter = &DefaultDebuginfoNameEmitter;
+                    assert_eq!(
+                        name,
+                        compute_debuginfo_type_name_with_emitter(tcx, ty, &opts, emitter)
+                    );
+                    if style == DebuginfoNameStyle::Rust {
+                        assert_eq!(name, compute_debuginfo_type_name(tcx, ty, true));
+                        assert_eq!(
+                            name,
+                            compute_debuginfo_type_name_symbol(tcx, ty, true).as_str()
+                        );
+                    }
+                    println!("{:?} (default options): {}", style, name);
+                }
+            }
+
+            // The capacity hint: the buffer for the name of a generic-heavy type must not have to
+            // grow more often while the name is built than with the old fixed capacity.
+            let def_id = find_item("capacity");
+            let ty = tcx.erase_late_bound_regions(tcx.fn_sig(def_id)).inputs()[0];
+            let name = compute_debuginfo_type_name(tcx, ty, true);
+            let hint = debuginfo_type_name_capacity_hint(ty);
+            let flat_reallocations = count_reallocations(&name, 64);
+            let hinted_reallocations = count_reallocations(&name, hint);
+            assert!(hinted_reallocations <= flat_reallocations);
+            println!("Rust: {}", name);
+            println!("The capacity hint takes no more reallocations than a capacity of 64: pass");
+
+            // Naming a type that is nested 10,000 levels deep must neither overflow the stack
+            // nor produce a name of that depth.
+            let mut deep = tcx.types.u8;
+            for _ in 0..10_000 {
+                deep = tcx.intern_tup(&[deep]);
+            }
+            for style in [DebuginfoNameStyle::CppLike, DebuginfoNameStyle::Rust] {
+                let opts =
+                    DebuginfoTypeNameOptions { style, ..DebuginfoTypeNameOptions::new(tcx, true) };
+                let name = compute_debuginfo_type_name_with_options(tcx, deep, &opts);
+                let marker =
+                    if style == DebuginfoNameStyle::CppLike { "truncated$" } else { "{truncated}" };
+                assert_eq!(name.matches(marker).count(), 1, "{}", name);
+                println!("{:?}: {} bytes for a depth of {}", style, name.len(), opts.max_depth);
+            }
+
+            // The fast path for primitive types, which are the most common ones by far: the
+            // names have to be the same as the ones computed the general way, but computing
+            // them should take less memory.
+            let t = &tcx.types;
+            let primitives = [
+                t.bool, t.char, t.i8, t.i16, t.i32, t.i64, t.i128, t.isize, t.u8, t.u16, t.u32,
+                t.u64, t.u128, t.usize, t.f32, t.f64, t.str_, t.never,
+            ];
+            let opts = DebuginfoTypeNameOptions::new(tcx, true);
+            let (mut general_path_bytes, mut fast_path_bytes) = (0, 0);
+            for ty in primitives {
+                let before = ALLOCATED_BYTES.load(Ordering::Relaxed);
+                let general_path_name = compute_debuginfo_type_name_with_emitter(
+                    tcx,
+                    ty,
+                    &opts,
+                    &DefaultDebuginfoNameEmitter,
+                );
+                let between = ALLOCATED_BYTES.load(Ordering::Relaxed);
+                let fast_path_name = compute_debuginfo_type_name_with_options(tcx, ty, &opts);
+                let after = ALLOCATED_BYTES.load(Ordering::Relaxed);
+                assert_eq!(general_path_name, fast_path_name);
+                general_path_bytes += between - before;
+                fast_path_bytes += after - between;
+            }
+            assert!(fast_path_bytes < general_path_bytes);
+            println!("The fast path for primitive types allocates less: pass");
+        });
+
+        Compilation::Continue
+    }
+}
+
+fn count_reallocations(name: &str, capacity: usize) -> usize {
+    let mut buffer = String::with_capacity(capacity);
+    let mut reallocations = 0;
+    for c in name.chars() {
+        let old_capacity = buffer.capacity();
+        buffer.push(c);
+        if buffer.capacity() != old_capacity {
+            reallocations += 1;
+        }
+    }
+    reallocations
+}
+
+// Replaces the hash values in the names of constants by `<hash>`.
+fn mask_const_hashes(name: &str) -> String {
+    let mut masked = String::new();
+    let mut rest = name;
+    while let Some(pos) = rest.find("CONST") {
+        let (before, after) = rest.split_at(pos + "CONST".len() + 1);
+        masked.push_str(before);
+        masked.push_str("<hash>");
+        rest = after.trim_start_matches(|c: char| c.is_ascii_hexdigit());
+    }
+    masked.push_str(rest);
+    masked
+}
+
+// Names `u8` `byte` and leaves everything else as it is.
+struct ByteEmitter;
+
+impl<'tcx> DebuginfoNameEmitter<'tcx> for ByteEmitter {
+    fn push_primitive(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        if t == cx.tcx().types.u8 {
+            output.push_str("byte");
+        } else {
+            cx.push_default(t, output);
+        }
+    }
+}
+
+// Names every ADT after itself, which makes it recursive.
+struct CyclicEmitter;
+
+impl<'tcx> DebuginfoNameEmitter<'tcx> for CyclicEmitter {
+    fn push_adt(&self, cx: &mut DebuginfoNameCx<'_, 'tcx>, t: Ty<'tcx>, output: &mut String) {
+        output.push_str("Cyclic<");
+        cx.push_nested(t, output);
+        output.push('>');
+    }
+}
+
+#[derive(Default)]
+struct ByteSink(Vec<u8>);
+
+impl std::fmt::Write for ByteSink {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+#![feature(adt_const_params, decl_macro, generic_const_exprs)]
+#![allow(incomplete_features)]
+
+use std::collections::HashMap;
+
+pub fn names(_: Vec<u8>, _: &u32) {}
+
+pub struct Sep<const C: char>;
+
+pub struct Label<const S: &'static str>;
+
+// Names that trip up simple guesses at their style.
+pub fn tricky_styles(_: (u8,), _: Sep<','>, _: Label<"a$b">, _: (u8, u16), _: &[u8]) {}
+
+pub fn lifetimes<'a>(_: &'a u8, _: &'static mut str, _: &u16) {}
+
+pub trait Trait {}
+
+pub trait WithLen {
+    const LEN: usize;
+}
+
+pub fn dyn_lifetimes<'a>(_: Box<dyn Trait + Send + 'static>, _: &'a (dyn std::fmt::Debug + 'a)) {}
+
+pub fn dyn_parens(_: &dyn Trait, _: &dyn Sync, _: &(dyn Trait + Send)) {}
+
+pub fn auto_traits(_: &(dyn Send + Sync + Unpin)) {}
+
+pub fn short_names(_: HashMap<u32, String>, _: bool, _: (u8, Vec<u8>)) {}
+
+pub fn opaque_debug_send() -> impl std::fmt::Debug + Send {
+    0u8
+}
+
+pub fn opaque_send_debug() -> impl Send + std::fmt::Debug {
+    0u8
+}
+
+pub trait Bindings {
+    type Second;
+    type First;
+}
+
+pub fn binding_order(_: &dyn Bindings<Second = u16, First = u8>) {}
+
+pub fn projection<I: Iterator>(_: I::Item) {}
+
+pub fn niche(_: Option<std::num::NonZeroU8>) {}
+
+pub fn other_tuple(_: (u8, u16)) {}
+
+// Thanks to def-site hygiene, the `T` passed in does not clash with the macro's own `T`.
+pub macro colliding_params($param:ident) {
+    pub struct Colliding<T, const $param: usize>(T);
+}
+
+colliding_params!(T);
+
+pub fn capacity(_: HashMap<String, Vec<(u64, u64)>>) {}
+
+pub fn opaque() -> impl Iterator<Item = u8> {
+    std::iter::empty()
+}
+
+pub fn array_lengths<const N: usize>(_: [u8; N], _: [u8; N + 1])
+where
+    [(); N + 1]:,
+{
+}
+
+pub fn vtables(_: &dyn std::fmt::Display, _: u32, _: String) {}
+
+pub fn shapes<T>(
+    _: T,
+    _: Vec<u8>,
+    _: (u8, u16),
+    _: [u8; 2],
+    _: &[u8],
+    _: *const dyn Send,
+    _: fn(u8),
+) {
+    let _closure = || {};
+}
+
+pub fn impl_traits(iter: impl Iterator<Item = u8>) -> impl Iterator<Item = u8> {
+    iter
+}
+
+pub fn higher_ranked(_: for<'a> fn(&'a u8) -> &'a u8, _: fn(&u8)) {}
+
+pub enum TooBig {
+    A([u8; usize::MAX]),
+    B,
+}
+
+pub struct Fields<T, U> {
+    pub value: T,
+    pub pair: (T, U),
+    pub items: Vec<U>,
+    pub next: Option<Box<Fields<T, U>>>,
+}
+
+pub fn fields(_: Fields<u8, String>) {}
+
+pub fn params<T, U>(_: T, _: (U, T)) {}
+
+pub fn defids<K, V>(_: Vec<HashMap<K, V>>) {}
+
+pub fn default_options(
+    _: Vec<String>,
+    _: (u8, &str),
+    _: &mut [u16],
+    _: &dyn Fn(u8) -> u8,
+    _: fn(u8) -> bool,
+) {
+}
+
+pub struct Pair<A, B>(A, B);
+
+pub fn siblings(_: Pair<Vec<u8>, Vec<u8>>, _: Pair<(u8, u16), (u8, u16)>) {}
+
+pub struct Cached;
+
+pub fn cached(_: Vec<HashMap<u32, Cached>>) {}
+
+pub mod geometry {
+    pub struct Circle;
+
+    pub mod round {
+        pub struct Ellipse;
+    }
+
+    pub fn relative(_: (Circle, round::Ellipse, Vec<Circle>, crate::outer::inner::Nested)) {}
+}
+
+pub mod outer {
+    pub mod inner {
+        pub struct Nested;
+    }
+}
+
+fn main() {}
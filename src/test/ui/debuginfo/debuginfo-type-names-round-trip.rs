@@ -0,0 +1,68 @@
+// build-pass
+// compile-flags: -Cdebuginfo=2 -Zverify-debuginfo-type-names
+// Checks that the Rust-style debuginfo type names of a broad set of types can be parsed
+// back by `parse_debuginfo_type_name` and printed again without changes.
+
+#![allow(dead_code, unused_variables)]
+#![feature(adt_const_params, extern_types, repr_simd)]
+#![allow(incomplete_features)]
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+pub struct Struct;
+pub struct Generic<T, U>(PhantomData<(T, U)>);
+pub struct Consts<const B: bool, const C: char, const I: i32, const F: f64>;
+pub enum Enum<T> {
+    A,
+    B(T),
+}
+
+pub trait Trait<T> {
+    type Assoc;
+}
+
+impl<T> Trait<T> for u8 {
+    type Assoc = T;
+}
+
+extern "C" {
+    type Foreign;
+}
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct F32x4(f32, f32, f32, f32);
+
+fn generic<T>(x: T) -> T {
+    x
+}
+
+extern "C" {
+    fn variadic(_: *const u8, ...) -> i32;
+}
+
+fn main() {
+    let unit = ();
+    let tuple = (1u8, Struct, Enum::B(2u16), 'c', 1.0f64);
+    let nested = ((Struct, ()), [0u8; 4]);
+    let generic_struct: Generic<Vec<HashMap<u32, String>>, Option<&'static str>> =
+        Generic(PhantomData);
+    let consts: Consts<true, '\'', -7, 3.14> = Consts;
+    let refs = (&Struct, &mut 0i32 as &mut i32, &[1u8, 2][..], "str");
+    let ptrs: (*const u8, *mut Struct, *const Foreign) =
+        (std::ptr::null(), std::ptr::null_mut(), std::ptr::null());
+    let dyn_principal: &dyn Debug = &0u8;
+    let dyn_auto: Box<dyn Send + Sync> = Box::new(0u8);
+    let dyn_both: &(dyn Trait<u16, Assoc = u16> + Send) = &0u8;
+    let fns = (
+        generic::<u8> as fn(u8) -> u8,
+        variadic as unsafe extern "C" fn(*const u8, ...) -> i32,
+        main as fn(),
+    );
+    let never: Option<fn() -> !> = None;
+    let closure = |x: u8| x + tuple.0;
+    let closure_tuple = (closure, 0usize);
+    let simd = (F32x4(1.0, 2.0, 3.0, 4.0), [F32x4(0.0, 0.0, 0.0, 0.0); 2]);
+}